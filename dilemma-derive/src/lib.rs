@@ -0,0 +1,149 @@
+//! Derives for `dilemma`, which re-exports them.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, LitStr};
+
+/// Implements `CustomType` for a fieldless enum, and adds an `as_str` method that returns the
+/// label stored in the database.
+///
+/// `#[sql_enum(type_name = "user_status")]` binds the enum as a value of that Postgres enum,
+/// and `#[sql_enum(text)]` binds it as text. Variants are stored as their names in snake case,
+/// with a run of capitals counting as one word, unless they have `#[sql_enum(rename = "...")]`.
+#[proc_macro_derive(SqlEnum, attributes(sql_enum))]
+pub fn derive_sql_enum(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_sql_enum(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+enum Storage {
+    TypeName(LitStr),
+    Text,
+}
+
+fn expand_sql_enum(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => return Err(Error::new_spanned(&input, "`SqlEnum` only supports enums")),
+    };
+
+    let mut storage = None;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("sql_enum")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("type_name") {
+                storage = Some(Storage::TypeName(meta.value()?.parse()?));
+                Ok(())
+            } else if meta.path.is_ident("text") {
+                storage = Some(Storage::Text);
+                Ok(())
+            } else {
+                Err(meta.error("expected `type_name = \"...\"` or `text`"))
+            }
+        })?;
+    }
+    let storage = storage.ok_or_else(|| {
+        Error::new_spanned(
+            name,
+            "missing `#[sql_enum(type_name = \"...\")]` or `#[sql_enum(text)]`",
+        )
+    })?;
+
+    let mut arms = Vec::new();
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(
+                variant,
+                "`SqlEnum` variants can't have fields",
+            ));
+        }
+
+        let mut label = snake_case(&variant.ident.to_string());
+        for attr in variant
+            .attrs
+            .iter()
+            .filter(|a| a.path().is_ident("sql_enum"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    label = meta.value()?.parse::<LitStr>()?.value();
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `rename = \"...\"`"))
+                }
+            })?;
+        }
+
+        let ident = &variant.ident;
+        arms.push(quote! { #name::#ident => #label });
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let as_str = quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// The label stored in the database.
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    #(#arms,)*
+                }
+            }
+        }
+    };
+
+    let custom_type = match storage {
+        Storage::Text => quote! {
+            impl #impl_generics ::dilemma::CustomType for #name #ty_generics #where_clause {
+                type SqlType = ::dilemma::sql_types::Text;
+
+                fn to_bind(&self) -> ::dilemma::Bind {
+                    ::dilemma::Bind::String(self.as_str().to_string())
+                }
+            }
+        },
+        Storage::TypeName(type_name) => quote! {
+            impl #impl_generics ::dilemma::CustomType for #name #ty_generics #where_clause {
+                type SqlType = Self;
+
+                fn to_bind(&self) -> ::dilemma::Bind {
+                    ::dilemma::Bind::Custom {
+                        type_name: #type_name,
+                        bytes: self.as_str().as_bytes().to_vec(),
+                    }
+                }
+            }
+
+            impl #impl_generics ::dilemma::sql_types::ArrayElement for #name #ty_generics #where_clause {
+                const NAME: &'static str = #type_name;
+            }
+        },
+    };
+
+    Ok(quote! {
+        #as_str
+        #custom_type
+    })
+}
+
+// `HTTPError` becomes `http_error`: a new word starts at a capital that follows a lowercase letter
+// or digit, or that ends a run of capitals followed by a lowercase letter
+fn snake_case(ident: &str) -> String {
+    let chars = ident.chars().collect::<Vec<_>>();
+    let mut out = String::new();
+    for (idx, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && idx > 0 {
+            let prev = chars[idx - 1];
+            let next_is_lower = chars.get(idx + 1).is_some_and(|next| next.is_lowercase());
+            if prev.is_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_uppercase() && next_is_lower)
+            {
+                out.push('_');
+            }
+        }
+        out.extend(c.to_lowercase());
+    }
+    out
+}
@@ -1,26 +1,89 @@
+use crate::dialect::{Dialect, Feature, RenderMode};
+use crate::Error;
+use std::cmp::Ordering;
 use std::fmt::{self, Write};
+use std::hash::{Hash, Hasher};
 use std::iter::IntoIterator;
 use std::vec::IntoIter;
 
-pub struct BindCount(usize);
+// Threaded through all SQL generation. Besides numbering the bind params it carries the dialect
+// and the first error that `fmt::Error` can't describe.
+pub struct BindCount<'a> {
+    count: usize,
+    dialect: &'a dyn Dialect,
+    error: Option<Error>,
+}
 
-impl BindCount {
-    pub fn new() -> Self {
-        Self(1)
+impl<'a> BindCount<'a> {
+    pub fn new(dialect: &'a dyn Dialect) -> Self {
+        Self {
+            count: 1,
+            dialect,
+            error: None,
+        }
     }
 
     fn next(&mut self) -> usize {
-        let count = self.0;
-        self.0 += 1;
+        let count = self.count;
+        self.count += 1;
         count
     }
 
     pub fn count(&self) -> usize {
-        self.0
+        self.count
     }
 
     pub fn write_sql<W: Write>(&mut self, f: &mut W) -> fmt::Result {
-        write!(f, "${}", self.next())
+        let index = self.next();
+        self.dialect.write_placeholder(f, index)
+    }
+
+    pub fn write_identifier<W: Write>(&self, f: &mut W, ident: &str) -> fmt::Result {
+        self.dialect.write_identifier(f, ident)
+    }
+
+    pub fn require(&mut self, feature: Feature) -> fmt::Result {
+        if self.dialect.supports(feature) {
+            Ok(())
+        } else {
+            self.fail(Error::Unsupported {
+                dialect: self.dialect.name(),
+                feature,
+            })
+        }
+    }
+
+    pub fn supports(&self, feature: Feature) -> bool {
+        self.dialect.supports(feature)
+    }
+
+    pub fn render_mode(&mut self, feature: Feature) -> Result<RenderMode, fmt::Error> {
+        if self.dialect.supports(feature) {
+            Ok(RenderMode::Native)
+        } else if self.dialect.emulates(feature) {
+            Ok(RenderMode::Emulated)
+        } else {
+            self.require(feature).map(|_| RenderMode::Native)
+        }
+    }
+
+    pub fn dialect_name(&self) -> &'static str {
+        self.dialect.name()
+    }
+
+    pub fn unbounded_limit(&self) -> &'static str {
+        self.dialect.unbounded_limit()
+    }
+
+    pub fn fail(&mut self, error: Error) -> fmt::Result {
+        if self.error.is_none() {
+            self.error = Some(error);
+        }
+        Err(fmt::Error)
+    }
+
+    pub fn into_error(self, err: fmt::Error) -> Error {
+        self.error.unwrap_or(Error::Fmt(err))
     }
 }
 
@@ -28,21 +91,33 @@ pub trait CollectBinds {
     fn collect_binds(&self, binds: &mut BindsInternal);
 }
 
-#[derive(Debug)]
-pub struct BindsInternal {
+// Carries the dialect so binds are collected in the same order as the placeholders are written,
+// including for constructs that are emulated by writing an expression more than once.
+pub struct BindsInternal<'a> {
     vec: Vec<Bind>,
+    dialect: &'a dyn Dialect,
 }
 
-impl BindsInternal {
-    pub fn with_capacity(capacity: usize) -> Self {
+impl<'a> BindsInternal<'a> {
+    pub fn with_capacity(capacity: usize, dialect: &'a dyn Dialect) -> Self {
         Self {
             vec: Vec::with_capacity(capacity),
+            dialect,
         }
     }
 
     pub fn push(&mut self, bind: Bind) {
         self.vec.push(bind);
     }
+
+    // Unsupported features have already failed rendering, so they don't need an error here
+    pub(crate) fn render_mode(&self, feature: Feature) -> RenderMode {
+        if !self.dialect.supports(feature) && self.dialect.emulates(feature) {
+            RenderMode::Emulated
+        } else {
+            RenderMode::Native
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -56,19 +131,153 @@ impl Iterator for Binds {
     fn next(&mut self) -> Option<Self::Item> {
         self.iter.next()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
 }
 
-impl From<BindsInternal> for Binds {
-    fn from(internal: BindsInternal) -> Self {
+impl ExactSizeIterator for Binds {}
+
+impl Binds {
+    #[cfg(feature = "postgres-types")]
+    pub(crate) fn as_slice(&self) -> &[Bind] {
+        self.iter.as_slice()
+    }
+}
+
+impl From<BindsInternal<'_>> for Binds {
+    fn from(internal: BindsInternal<'_>) -> Self {
         Binds {
             iter: internal.vec.into_iter(),
         }
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash)]
+/// A bind parameter.
+///
+/// `Date` is the number of days since 1970-01-01. `Time` is the number of microseconds since
+/// midnight, and `Timestamp` and `Timestamptz` the number of microseconds since
+/// 1970-01-01 00:00:00 (UTC for `Timestamptz`). `Numeric`, `Json` and `Jsonb` hold their text
+/// representation. `Interval` keeps months and days apart from the rest since their length
+/// varies. `Custom` holds a value of a user defined type in its binary format, which
+/// for Postgres enums is the label. `Array` holds the Postgres name of its element type so
+/// empty arrays can be typed too.
+#[derive(Debug, Clone)]
 pub enum Bind {
     String(String),
     I32(i32),
     U64(u64),
+    I16(i16),
+    I64(i64),
+    Bool(bool),
+    F32(f32),
+    F64(f64),
+    Numeric(String),
+    Bytes(Vec<u8>),
+    Date(i32),
+    Time(i64),
+    Timestamp(i64),
+    Timestamptz(i64),
+    Uuid([u8; 16]),
+    Json(String),
+    Jsonb(String),
+    Interval {
+        months: i32,
+        days: i32,
+        micros: i64,
+    },
+    Custom {
+        type_name: &'static str,
+        bytes: Vec<u8>,
+    },
+    Array {
+        element_type: &'static str,
+        values: Vec<Bind>,
+    },
+}
+
+// Floats are compared by their bits so binds can be `Eq`, `Ord` and `Hash`
+#[derive(Eq, PartialEq, PartialOrd, Ord, Hash)]
+enum BindKey<'a> {
+    String(&'a str),
+    I32(i32),
+    U64(u64),
+    I16(i16),
+    I64(i64),
+    Bool(bool),
+    F32(u32),
+    F64(u64),
+    Numeric(&'a str),
+    Bytes(&'a [u8]),
+    Date(i32),
+    Time(i64),
+    Timestamp(i64),
+    Timestamptz(i64),
+    Uuid(&'a [u8; 16]),
+    Json(&'a str),
+    Jsonb(&'a str),
+    Interval(i32, i32, i64),
+    Custom(&'a str, &'a [u8]),
+    Array(&'a str, Vec<BindKey<'a>>),
+}
+
+impl Bind {
+    fn key(&self) -> BindKey<'_> {
+        match self {
+            Bind::String(value) => BindKey::String(value),
+            Bind::I32(value) => BindKey::I32(*value),
+            Bind::U64(value) => BindKey::U64(*value),
+            Bind::I16(value) => BindKey::I16(*value),
+            Bind::I64(value) => BindKey::I64(*value),
+            Bind::Bool(value) => BindKey::Bool(*value),
+            Bind::F32(value) => BindKey::F32(value.to_bits()),
+            Bind::F64(value) => BindKey::F64(value.to_bits()),
+            Bind::Numeric(value) => BindKey::Numeric(value),
+            Bind::Bytes(value) => BindKey::Bytes(value),
+            Bind::Date(value) => BindKey::Date(*value),
+            Bind::Time(value) => BindKey::Time(*value),
+            Bind::Timestamp(value) => BindKey::Timestamp(*value),
+            Bind::Timestamptz(value) => BindKey::Timestamptz(*value),
+            Bind::Uuid(value) => BindKey::Uuid(value),
+            Bind::Json(value) => BindKey::Json(value),
+            Bind::Jsonb(value) => BindKey::Jsonb(value),
+            Bind::Interval {
+                months,
+                days,
+                micros,
+            } => BindKey::Interval(*months, *days, *micros),
+            Bind::Custom { type_name, bytes } => BindKey::Custom(type_name, bytes),
+            Bind::Array {
+                element_type,
+                values,
+            } => BindKey::Array(element_type, values.iter().map(Bind::key).collect()),
+        }
+    }
+}
+
+impl PartialEq for Bind {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Bind {}
+
+impl PartialOrd for Bind {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Bind {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl Hash for Bind {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state)
+    }
 }
@@ -0,0 +1,41 @@
+use crate::row_locking::RowLocking;
+use crate::{count, from, star, IntoSubQuery, QueryDsl, QueryWithSelect};
+
+impl<T> QueryWithSelect<T> {
+    /// A query for the number of rows this query returns.
+    ///
+    /// Grouped, distinct and limited queries are counted in a sub query, as in
+    /// `SELECT count(*) FROM (<query>) "sub"`, since replacing their select list would count
+    /// the rows of each group or ignore the limit. So are queries with `HAVING`, or with an
+    /// aggregate or raw SQL in their select list, since those aggregate every row into one.
+    /// Other queries get `count(*)` as their select list. Row locks are dropped and so is the
+    /// `ORDER BY` unless the limit depends on it. An `EXPLAIN` applies to the count query as a
+    /// whole.
+    pub fn count_query(self) -> QueryWithSelect<T> {
+        let QueryWithSelect {
+            mut query,
+            selection,
+        } = self;
+        query.row_locking = RowLocking::new();
+
+        let limited = query.limit.is_some() || query.offset.is_some();
+        if !limited {
+            query = query.remove_order_by();
+        }
+
+        if limited
+            || query.group.is_some()
+            || query.having.is_some()
+            || query.distinct.is_some()
+            || selection.may_aggregate()
+        {
+            // `EXPLAIN` is only valid at the top of a statement
+            let explain = query.explain.take();
+            let mut outer = from(query.select(selection).alias("sub"));
+            outer.explain = explain;
+            outer.select(count(star()))
+        } else {
+            query.select(count(star()))
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use crate::binds::{BindCount, BindsInternal, CollectBinds};
 use crate::from::{CastVecSubQuery, SubQuery};
 use crate::write_sql::WriteSql;
+use crate::Error;
 use itertools::{Itertools, Position};
 use std::fmt::{self, Write};
 
@@ -17,6 +18,12 @@ impl<T, K> From<SubQuery<K>> for Ctes<T> {
     }
 }
 
+impl<T> PartialEq for Ctes<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.queries == other.queries
+    }
+}
+
 impl<T> Default for Ctes<T> {
     fn default() -> Self {
         Ctes {
@@ -26,6 +33,21 @@ impl<T> Default for Ctes<T> {
 }
 
 impl<T> Ctes<T> {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.queries.is_empty()
+    }
+
+    pub(crate) fn iter(&self) -> std::slice::Iter<'_, SubQuery<T>> {
+        self.queries.iter()
+    }
+
+    pub(crate) fn check(&self) -> Result<(), Error> {
+        for sub_query in &self.queries {
+            sub_query.query.check()?;
+        }
+        Ok(())
+    }
+
     pub fn cast_to<K>(self) -> Ctes<K> {
         Ctes {
             queries: self.queries.cast_to::<K>(),
@@ -44,12 +66,14 @@ impl<T> WriteSql for &Ctes<T> {
         for query in self.queries.iter().with_position() {
             match query {
                 Position::First(sub_query) | Position::Middle(sub_query) => {
-                    write!(f, "\"{}\" AS (", sub_query.alias)?;
+                    bind_count.write_identifier(f, &sub_query.alias)?;
+                    write!(f, " AS (")?;
                     sub_query.query.write_sql(f, bind_count)?;
                     write!(f, "), ")?;
                 }
                 Position::Last(sub_query) | Position::Only(sub_query) => {
-                    write!(f, "\"{}\" AS (", sub_query.alias)?;
+                    bind_count.write_identifier(f, &sub_query.alias)?;
+                    write!(f, " AS (")?;
                     sub_query.query.write_sql(f, bind_count)?;
                     write!(f, ") ")?;
                 }
@@ -82,7 +106,7 @@ impl<T> Extend<SubQuery<T>> for Ctes<T> {
     where
         K: IntoIterator<Item = SubQuery<T>>,
     {
-        self.queries.extend(iter.into_iter())
+        self.queries.extend(iter)
     }
 }
 
@@ -0,0 +1,42 @@
+use crate::binds::Bind;
+use crate::{Expr, IntoExpr};
+
+/// A Rust type that is bound as a value of its own SQL type.
+///
+/// `SqlType` is the marker type used for the column in `table!`, which for a Rust enum is usually
+/// the enum itself. Fieldless enums can derive it:
+///
+/// ```
+/// #[derive(dilemma::SqlEnum)]
+/// #[sql_enum(type_name = "user_status")]
+/// pub enum Status {
+///     Active,
+///     #[sql_enum(rename = "banned_forever")]
+///     Banned,
+/// }
+///
+/// assert_eq!(Status::Banned.as_str(), "banned_forever");
+/// ```
+///
+/// With a type name the enum is its own SQL type and binds are `Bind::Custom` with that type
+/// name. With `#[sql_enum(text)]` the enum binds as `Bind::String` and compares with text
+/// columns.
+pub trait CustomType {
+    type SqlType;
+
+    fn to_bind(&self) -> Bind;
+}
+
+impl<T: CustomType> From<T> for Bind {
+    fn from(value: T) -> Self {
+        value.to_bind()
+    }
+}
+
+impl<T: CustomType> IntoExpr for T {
+    type SqlType = T::SqlType;
+
+    fn into_expr(self) -> Expr {
+        Expr::Bind(self.to_bind())
+    }
+}
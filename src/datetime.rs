@@ -0,0 +1,47 @@
+// Text formatting of date and time binds for databases that store them as text.
+
+const MICROS_PER_SECOND: i64 = 1_000_000;
+const MICROS_PER_DAY: i64 = 86_400 * MICROS_PER_SECOND;
+
+// `YYYY-MM-DD`
+pub(crate) fn format_date(days_since_epoch: i32) -> String {
+    let (year, month, day) = civil_from_days(i64::from(days_since_epoch));
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+// `HH:MM:SS`, with microseconds if there are any
+pub(crate) fn format_time(micros_since_midnight: i64) -> String {
+    let seconds = micros_since_midnight / MICROS_PER_SECOND;
+    let micros = micros_since_midnight % MICROS_PER_SECOND;
+    let mut out = format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    );
+    if micros != 0 {
+        out.push_str(&format!(".{:06}", micros));
+    }
+    out
+}
+
+// `YYYY-MM-DD HH:MM:SS`, with microseconds if there are any
+pub(crate) fn format_timestamp(micros_since_epoch: i64) -> String {
+    let days = micros_since_epoch.div_euclid(MICROS_PER_DAY);
+    let micros = micros_since_epoch.rem_euclid(MICROS_PER_DAY);
+    format!("{} {}", format_date(days as i32), format_time(micros))
+}
+
+// http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
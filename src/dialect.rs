@@ -0,0 +1,255 @@
+use std::fmt::{self, Write};
+
+/// The SQL dialect used to render a query.
+///
+/// The provided methods render Postgres flavored SQL, so a dialect only has to override what
+/// differs.
+pub trait Dialect {
+    fn name(&self) -> &'static str;
+
+    /// Write the placeholder for the bind parameter at `index`. Indices start at 1.
+    ///
+    /// Binds are always positional, so named styles such as `:p1` or `@p1` have to derive the
+    /// name from `index`.
+    fn write_placeholder(&self, f: &mut dyn Write, index: usize) -> fmt::Result {
+        write!(f, "${}", index)
+    }
+
+    fn write_identifier(&self, f: &mut dyn Write, ident: &str) -> fmt::Result {
+        write!(f, "\"{}\"", ident.replace('"', "\"\""))
+    }
+
+    /// Whether the dialect can render `feature`. Rendering a query that uses an unsupported
+    /// feature fails with `Error::Unsupported` unless the dialect `emulates` it.
+    fn supports(&self, feature: Feature) -> bool {
+        let _ = feature;
+        true
+    }
+
+    /// Whether an unsupported `feature` should be rewritten into SQL the dialect understands.
+    /// Only `NullsPosition`, `DistinctOn`, `OffsetWithoutLimit`, `RowValueInList`,
+    /// `CustomTypes` and `NestedSetOperations` can be emulated.
+    ///
+    /// - `NullsPosition` becomes an extra `IS NULL` ordering.
+    /// - `DistinctOn` wraps the query in a sub query that numbers the rows of each partition
+    ///   with `ROW_NUMBER()`, and keeps the first row. The result has extra columns for the row
+    ///   number and the `ORDER BY` expressions. Raw orderings can't be emulated.
+    /// - `OffsetWithoutLimit` adds `LIMIT` with `unbounded_limit`.
+    /// - `RowValueInList` writes the list as `VALUES`.
+    /// - `CustomTypes` binds values of custom types without casting them to their type.
+    /// - `NestedSetOperations` selects from a sub query for each operand that needs parentheses.
+    ///   Set operators are then assumed to be evaluated left to right, so every nested right hand
+    ///   side is wrapped.
+    fn emulates(&self, feature: Feature) -> bool {
+        let _ = feature;
+        false
+    }
+
+    /// The `LIMIT` used when `OffsetWithoutLimit` is emulated.
+    fn unbounded_limit(&self) -> &'static str {
+        "ALL"
+    }
+
+    fn max_binds(&self) -> usize {
+        u16::MAX as usize
+    }
+}
+
+/// Constructs that not every dialect supports.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Feature {
+    DistinctOn,
+    NullsPosition,
+    OffsetWithoutLimit,
+    ForUpdate,
+    ForNoKeyUpdate,
+    ForShare,
+    ForKeyShare,
+    NoWait,
+    SkipLocked,
+    ExplainAnalyze,
+    UnionDistinct,
+    IntersectAll,
+    IntersectDistinct,
+    ExceptAll,
+    ExceptDistinct,
+    Arrays,
+    Jsonb,
+    FullTextSearch,
+    Intervals,
+    AtTimeZone,
+    RowValueInList,
+    CustomTypes,
+    NestedSetOperations,
+    DateTrunc,
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sql = match self {
+            Feature::DistinctOn => "DISTINCT ON",
+            Feature::NullsPosition => "NULLS FIRST/LAST",
+            Feature::OffsetWithoutLimit => "OFFSET without LIMIT",
+            Feature::ForUpdate => "FOR UPDATE",
+            Feature::ForNoKeyUpdate => "FOR NO KEY UPDATE",
+            Feature::ForShare => "FOR SHARE",
+            Feature::ForKeyShare => "FOR KEY SHARE",
+            Feature::NoWait => "NOWAIT",
+            Feature::SkipLocked => "SKIP LOCKED",
+            Feature::ExplainAnalyze => "EXPLAIN ANALYZE",
+            Feature::UnionDistinct => "UNION DISTINCT",
+            Feature::IntersectAll => "INTERSECT ALL",
+            Feature::IntersectDistinct => "INTERSECT DISTINCT",
+            Feature::ExceptAll => "EXCEPT ALL",
+            Feature::ExceptDistinct => "EXCEPT DISTINCT",
+            Feature::Arrays => "arrays",
+            Feature::Jsonb => "jsonb",
+            Feature::FullTextSearch => "full text search",
+            Feature::Intervals => "intervals",
+            Feature::AtTimeZone => "AT TIME ZONE",
+            Feature::RowValueInList => "row value IN lists",
+            Feature::CustomTypes => "custom types",
+            Feature::NestedSetOperations => "parenthesized set operation operands",
+            Feature::DateTrunc => "date_trunc",
+        };
+        write!(f, "{}", sql)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum RenderMode {
+    Native,
+    Emulated,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Postgres;
+
+impl Dialect for Postgres {
+    fn name(&self) -> &'static str {
+        "Postgres"
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MySql;
+
+impl Dialect for MySql {
+    fn name(&self) -> &'static str {
+        "MySQL"
+    }
+
+    fn write_placeholder(&self, f: &mut dyn Write, _: usize) -> fmt::Result {
+        write!(f, "?")
+    }
+
+    fn write_identifier(&self, f: &mut dyn Write, ident: &str) -> fmt::Result {
+        write!(f, "`{}`", ident.replace('`', "``"))
+    }
+
+    fn supports(&self, feature: Feature) -> bool {
+        match feature {
+            Feature::DistinctOn
+            | Feature::NullsPosition
+            | Feature::OffsetWithoutLimit
+            | Feature::ForNoKeyUpdate
+            | Feature::ForKeyShare
+            | Feature::Arrays
+            | Feature::Jsonb
+            | Feature::FullTextSearch
+            | Feature::Intervals
+            | Feature::AtTimeZone
+            | Feature::CustomTypes
+            | Feature::DateTrunc => false,
+            Feature::ForUpdate
+            | Feature::ForShare
+            | Feature::NoWait
+            | Feature::SkipLocked
+            | Feature::ExplainAnalyze
+            | Feature::UnionDistinct
+            | Feature::IntersectAll
+            | Feature::IntersectDistinct
+            | Feature::ExceptAll
+            | Feature::ExceptDistinct
+            | Feature::RowValueInList
+            | Feature::NestedSetOperations => true,
+        }
+    }
+
+    fn emulates(&self, feature: Feature) -> bool {
+        matches!(
+            feature,
+            Feature::DistinctOn
+                | Feature::NullsPosition
+                | Feature::OffsetWithoutLimit
+                | Feature::CustomTypes
+        )
+    }
+
+    // The largest possible limit, as recommended by the MySQL docs
+    fn unbounded_limit(&self) -> &'static str {
+        "18446744073709551615"
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sqlite;
+
+impl Dialect for Sqlite {
+    fn name(&self) -> &'static str {
+        "SQLite"
+    }
+
+    fn write_placeholder(&self, f: &mut dyn Write, _: usize) -> fmt::Result {
+        write!(f, "?")
+    }
+
+    fn supports(&self, feature: Feature) -> bool {
+        match feature {
+            Feature::NullsPosition => true,
+            Feature::DistinctOn
+            | Feature::OffsetWithoutLimit
+            | Feature::ForUpdate
+            | Feature::ForNoKeyUpdate
+            | Feature::ForShare
+            | Feature::ForKeyShare
+            | Feature::NoWait
+            | Feature::SkipLocked
+            | Feature::ExplainAnalyze
+            | Feature::UnionDistinct
+            | Feature::IntersectAll
+            | Feature::IntersectDistinct
+            | Feature::ExceptAll
+            | Feature::ExceptDistinct
+            | Feature::Arrays
+            | Feature::Jsonb
+            | Feature::FullTextSearch
+            | Feature::Intervals
+            | Feature::AtTimeZone
+            | Feature::RowValueInList
+            | Feature::CustomTypes
+            | Feature::NestedSetOperations
+            | Feature::DateTrunc => false,
+        }
+    }
+
+    // Row values on the left of `IN` need a sub query on the right, which `VALUES` is
+    fn emulates(&self, feature: Feature) -> bool {
+        matches!(
+            feature,
+            Feature::DistinctOn
+                | Feature::OffsetWithoutLimit
+                | Feature::RowValueInList
+                | Feature::CustomTypes
+                | Feature::NestedSetOperations
+        )
+    }
+
+    fn unbounded_limit(&self) -> &'static str {
+        "-1"
+    }
+
+    fn max_binds(&self) -> usize {
+        32766
+    }
+}
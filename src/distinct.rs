@@ -1,18 +1,50 @@
 use crate::binds::{BindCount, BindsInternal, CollectBinds};
-use crate::{write_sql::WriteSql, Column};
+use crate::dialect::Feature;
+use crate::validate::{CollectTableRefs, TableRef};
+use crate::{write_sql::WriteSql, Column, Error, Expr, Order};
 use std::fmt::{self, Write};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Distinct {
     EachRow,
     On(Vec<Column>),
 }
 
+impl Distinct {
+    // Postgres requires the leftmost `ORDER BY` expressions to be the `DISTINCT ON` expressions.
+    // Raw orderings cannot be checked so they are assumed to match, while other expressions never
+    // match a column.
+    pub(crate) fn check(&self, order: &Order) -> Result<(), Error> {
+        let cols = match self {
+            Distinct::EachRow => return Ok(()),
+            Distinct::On(cols) => cols,
+        };
+
+        let matches =
+            order
+                .orderings()
+                .iter()
+                .take(cols.len())
+                .all(|ordering| match ordering.expr() {
+                    Some(Expr::Column(col)) => cols.contains(col),
+                    Some(_) => false,
+                    None => true,
+                });
+
+        if matches {
+            Ok(())
+        } else {
+            Err(Error::DistinctOnWithoutMatchingOrderBy)
+        }
+    }
+}
+
 impl WriteSql for &Distinct {
     fn write_sql<W: Write>(self, f: &mut W, bind_count: &mut BindCount) -> fmt::Result {
         match self {
             Distinct::EachRow => write!(f, "DISTINCT "),
             Distinct::On(cols) => {
+                bind_count.require(Feature::DistinctOn)?;
                 write!(f, "DISTINCT ON (")?;
                 cols.iter().write_sql(f, bind_count)?;
                 write!(f, ") ")?;
@@ -25,3 +57,12 @@ impl WriteSql for &Distinct {
 impl CollectBinds for Distinct {
     fn collect_binds(&self, _: &mut BindsInternal) {}
 }
+
+impl CollectTableRefs for Distinct {
+    fn collect_table_refs<'a>(&'a self, refs: &mut Vec<TableRef<'a>>) {
+        match self {
+            Distinct::EachRow => {}
+            Distinct::On(cols) => refs.extend(cols.iter().map(TableRef::Column)),
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use crate::dialect::Feature;
+use std::fmt;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Error {
+    MergeConflict {
+        clause: &'static str,
+    },
+    JoinConflict {
+        table: String,
+    },
+    EmptySelect,
+    DistinctOnWithoutMatchingOrderBy,
+    ConflictingRowLocking,
+    TooManyBinds {
+        count: usize,
+        max: usize,
+    },
+    Fmt(fmt::Error),
+    TableNotInScope {
+        table: String,
+        column: Option<String>,
+    },
+    Unsupported {
+        dialect: &'static str,
+        feature: Feature,
+    },
+    UnsupportedEmulation {
+        dialect: &'static str,
+        feature: Feature,
+    },
+    InvalidCursor,
+    UnsupportedPaginationOrder,
+    UnsupportedSetOperationOrder,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::MergeConflict { clause } => {
+                write!(
+                    f,
+                    "both queries being merged have a conflicting `{}` clause",
+                    clause
+                )
+            }
+            Error::JoinConflict { table } => write!(
+                f,
+                "both queries being merged join `{}` differently",
+                table
+            ),
+            Error::EmptySelect => write!(f, "the select list is empty"),
+            Error::DistinctOnWithoutMatchingOrderBy => write!(
+                f,
+                "`DISTINCT ON` columns must match the leftmost `ORDER BY` columns"
+            ),
+            Error::ConflictingRowLocking => write!(
+                f,
+                "a query can only use one row locking mode and one of `NOWAIT` and `SKIP LOCKED`"
+            ),
+            Error::TooManyBinds { count, max } => write!(
+                f,
+                "query has {} bind parameters but at most {} are supported",
+                count, max
+            ),
+            Error::Fmt(err) => write!(f, "failed to write SQL: {}", err),
+            Error::TableNotInScope {
+                table,
+                column: Some(column),
+            } => write!(
+                f,
+                "column `{}`.`{}` references table `{}` which is not in scope",
+                table, column, table
+            ),
+            Error::TableNotInScope {
+                table,
+                column: None,
+            } => write!(f, "table `{}` is not in scope", table),
+            Error::Unsupported { dialect, feature } => {
+                write!(f, "{} does not support `{}`", dialect, feature)
+            }
+            Error::UnsupportedEmulation { dialect, feature } => write!(
+                f,
+                "{} can't emulate `{}` with raw SQL in the `ORDER BY`",
+                dialect, feature
+            ),
+            Error::InvalidCursor => write!(
+                f,
+                "the pagination cursor is invalid or doesn't match the `ORDER BY` of the query"
+            ),
+            Error::UnsupportedPaginationOrder => write!(
+                f,
+                "keyset pagination needs an `ORDER BY` without raw SQL, with `NULLS FIRST` or `NULLS LAST` for columns that can be NULL"
+            ),
+            Error::UnsupportedSetOperationOrder => write!(
+                f,
+                "the `ORDER BY` of a set operation can only use the columns of its result"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<fmt::Error> for Error {
+    fn from(err: fmt::Error) -> Self {
+        Error::Fmt(err)
+    }
+}
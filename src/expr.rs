@@ -1,37 +1,73 @@
 use crate::binds::BindCount;
 use crate::binds::{Bind, BindsInternal, CollectBinds};
-use crate::sql_types::{Integer, Text};
-use crate::{Column, Filter, WriteSql};
+use crate::dialect::RenderMode;
+use crate::sql_types::{
+    Array, ArrayElement, BigInt, Bool, Bytea, CompatibleWith, Double, Float, Integer, Nullable,
+    SmallInt, Text, Timestamptz,
+};
+use crate::validate::{CollectTableRefs, TableRef};
+use crate::{Column, DatePart, Feature, Filter, Select, Selection, WriteSql};
 use std::fmt::{self, Write};
+use std::marker::PhantomData;
+use std::time::{SystemTime, UNIX_EPOCH};
 
+// `x = NULL` is never true, so comparing with `None` becomes `x IS NULL` instead
+fn equality(lhs: Expr, op: BinOp, rhs: Expr) -> Filter {
+    let null_op = match op {
+        BinOp::Eq => UnOp::Null,
+        _ => UnOp::NotNull,
+    };
+
+    match (lhs, rhs) {
+        (expr, Expr::Null) | (Expr::Null, expr) => Filter::UnOp { expr, op: null_op },
+        (lhs, rhs) => Filter::BinOp { lhs, op, rhs },
+    }
+}
+
+#[allow(clippy::wrong_self_convention)]
 pub trait ExprDsl<SqlType>: Sized {
     fn eq<Rhs>(self, rhs: Rhs) -> Filter
     where
-        Rhs: IntoExpr<SqlType = SqlType>;
+        Rhs: IntoExpr,
+        Rhs::SqlType: CompatibleWith<SqlType>;
 
     fn ne<Rhs>(self, rhs: Rhs) -> Filter
     where
-        Rhs: IntoExpr<SqlType = SqlType>;
+        Rhs: IntoExpr,
+        Rhs::SqlType: CompatibleWith<SqlType>;
 
     fn gt<Rhs>(self, rhs: Rhs) -> Filter
     where
-        Rhs: IntoExpr<SqlType = SqlType>;
+        Rhs: IntoExpr,
+        Rhs::SqlType: CompatibleWith<SqlType>;
 
     fn ge<Rhs>(self, rhs: Rhs) -> Filter
     where
-        Rhs: IntoExpr<SqlType = SqlType>;
+        Rhs: IntoExpr,
+        Rhs::SqlType: CompatibleWith<SqlType>;
 
     fn lt<Rhs>(self, rhs: Rhs) -> Filter
     where
-        Rhs: IntoExpr<SqlType = SqlType>;
+        Rhs: IntoExpr,
+        Rhs::SqlType: CompatibleWith<SqlType>;
 
     fn le<Rhs>(self, rhs: Rhs) -> Filter
     where
-        Rhs: IntoExpr<SqlType = SqlType>;
+        Rhs: IntoExpr,
+        Rhs::SqlType: CompatibleWith<SqlType>;
 
-    fn is_not_null(self) -> Filter;
+    /// `self = ANY(rhs)`, where `rhs` is an array.
+    fn eq_any<Rhs>(self, rhs: Rhs) -> Filter
+    where
+        Rhs: IntoExpr,
+        Rhs::SqlType: CompatibleWith<Array<SqlType>>;
 
-    fn is_null(self) -> Filter;
+    /// `self IN (values)`, or `FALSE` if there are no values.
+    fn is_in<I>(self, values: I) -> Filter
+    where
+        I: IntoIterator,
+        I::Item: IntoExpr,
+        <I::Item as IntoExpr>::SqlType: CompatibleWith<SqlType>;
 }
 
 impl<T, SqlType> ExprDsl<SqlType> for T
@@ -40,29 +76,24 @@ where
 {
     fn eq<Rhs>(self, rhs: Rhs) -> Filter
     where
-        Rhs: IntoExpr<SqlType = SqlType>,
+        Rhs: IntoExpr,
+        Rhs::SqlType: CompatibleWith<SqlType>,
     {
-        Filter::BinOp {
-            lhs: self.into_expr(),
-            op: BinOp::Eq,
-            rhs: rhs.into_expr(),
-        }
+        equality(self.into_expr(), BinOp::Eq, rhs.into_expr())
     }
 
     fn ne<Rhs>(self, rhs: Rhs) -> Filter
     where
-        Rhs: IntoExpr<SqlType = SqlType>,
+        Rhs: IntoExpr,
+        Rhs::SqlType: CompatibleWith<SqlType>,
     {
-        Filter::BinOp {
-            lhs: self.into_expr(),
-            op: BinOp::Ne,
-            rhs: rhs.into_expr(),
-        }
+        equality(self.into_expr(), BinOp::Ne, rhs.into_expr())
     }
 
     fn gt<Rhs>(self, rhs: Rhs) -> Filter
     where
-        Rhs: IntoExpr<SqlType = SqlType>,
+        Rhs: IntoExpr,
+        Rhs::SqlType: CompatibleWith<SqlType>,
     {
         Filter::BinOp {
             lhs: self.into_expr(),
@@ -73,7 +104,8 @@ where
 
     fn ge<Rhs>(self, rhs: Rhs) -> Filter
     where
-        Rhs: IntoExpr<SqlType = SqlType>,
+        Rhs: IntoExpr,
+        Rhs::SqlType: CompatibleWith<SqlType>,
     {
         Filter::BinOp {
             lhs: self.into_expr(),
@@ -84,7 +116,8 @@ where
 
     fn lt<Rhs>(self, rhs: Rhs) -> Filter
     where
-        Rhs: IntoExpr<SqlType = SqlType>,
+        Rhs: IntoExpr,
+        Rhs::SqlType: CompatibleWith<SqlType>,
     {
         Filter::BinOp {
             lhs: self.into_expr(),
@@ -95,7 +128,8 @@ where
 
     fn le<Rhs>(self, rhs: Rhs) -> Filter
     where
-        Rhs: IntoExpr<SqlType = SqlType>,
+        Rhs: IntoExpr,
+        Rhs::SqlType: CompatibleWith<SqlType>,
     {
         Filter::BinOp {
             lhs: self.into_expr(),
@@ -104,6 +138,50 @@ where
         }
     }
 
+    fn eq_any<Rhs>(self, rhs: Rhs) -> Filter
+    where
+        Rhs: IntoExpr,
+        Rhs::SqlType: CompatibleWith<Array<SqlType>>,
+    {
+        Filter::BinOp {
+            lhs: self.into_expr(),
+            op: BinOp::Eq,
+            rhs: Expr::Any(Box::new(rhs.into_expr())),
+        }
+    }
+
+    fn is_in<I>(self, values: I) -> Filter
+    where
+        I: IntoIterator,
+        I::Item: IntoExpr,
+        <I::Item as IntoExpr>::SqlType: CompatibleWith<SqlType>,
+    {
+        let values = values
+            .into_iter()
+            .map(IntoExpr::into_expr)
+            .collect::<Vec<_>>();
+        if values.is_empty() {
+            Filter::False
+        } else {
+            Filter::In {
+                lhs: self.into_expr(),
+                values,
+            }
+        }
+    }
+}
+
+#[allow(clippy::wrong_self_convention)]
+pub trait NullableExprDsl: Sized {
+    fn is_not_null(self) -> Filter;
+
+    fn is_null(self) -> Filter;
+}
+
+impl<T, SqlType> NullableExprDsl for T
+where
+    T: IntoExpr<SqlType = Nullable<SqlType>>,
+{
     fn is_not_null(self) -> Filter {
         Filter::UnOp {
             expr: self.into_expr(),
@@ -119,27 +197,263 @@ where
     }
 }
 
-impl IntoExpr for i32 {
-    type SqlType = Integer;
+#[allow(clippy::wrong_self_convention)]
+pub trait ArrayExprDsl<Element>: Sized {
+    /// `self @> rhs`
+    fn contains<Rhs>(self, rhs: Rhs) -> Filter
+    where
+        Rhs: IntoExpr,
+        Rhs::SqlType: CompatibleWith<Array<Element>>;
+
+    /// `self <@ rhs`
+    fn is_contained_by<Rhs>(self, rhs: Rhs) -> Filter
+    where
+        Rhs: IntoExpr,
+        Rhs::SqlType: CompatibleWith<Array<Element>>;
+
+    /// `self && rhs`
+    fn overlaps<Rhs>(self, rhs: Rhs) -> Filter
+    where
+        Rhs: IntoExpr,
+        Rhs::SqlType: CompatibleWith<Array<Element>>;
+
+    /// The length of the first dimension. `NULL` for empty arrays.
+    fn array_length(self) -> TypedExpr<Nullable<Integer>>;
+
+    /// The element at `index`, which starts at 1. `NULL` if out of bounds.
+    fn index(self, index: i32) -> TypedExpr<Nullable<Element>>;
+}
+
+impl<T, Element> ArrayExprDsl<Element> for T
+where
+    T: IntoExpr<SqlType = Array<Element>>,
+{
+    fn contains<Rhs>(self, rhs: Rhs) -> Filter
+    where
+        Rhs: IntoExpr,
+        Rhs::SqlType: CompatibleWith<Array<Element>>,
+    {
+        Filter::BinOp {
+            lhs: self.into_expr(),
+            op: BinOp::Contains,
+            rhs: rhs.into_expr(),
+        }
+    }
+
+    fn is_contained_by<Rhs>(self, rhs: Rhs) -> Filter
+    where
+        Rhs: IntoExpr,
+        Rhs::SqlType: CompatibleWith<Array<Element>>,
+    {
+        Filter::BinOp {
+            lhs: self.into_expr(),
+            op: BinOp::ContainedBy,
+            rhs: rhs.into_expr(),
+        }
+    }
+
+    fn overlaps<Rhs>(self, rhs: Rhs) -> Filter
+    where
+        Rhs: IntoExpr,
+        Rhs::SqlType: CompatibleWith<Array<Element>>,
+    {
+        Filter::BinOp {
+            lhs: self.into_expr(),
+            op: BinOp::Overlaps,
+            rhs: rhs.into_expr(),
+        }
+    }
+
+    fn array_length(self) -> TypedExpr<Nullable<Integer>> {
+        TypedExpr::new(Expr::Function {
+            name: "array_length",
+            args: vec![self.into_expr(), Expr::Raw("1".to_string())],
+        })
+    }
+
+    fn index(self, index: i32) -> TypedExpr<Nullable<Element>> {
+        TypedExpr::new(Expr::Index {
+            expr: Box::new(self.into_expr()),
+            index,
+        })
+    }
+}
+
+/// An expression with a known SQL type, such as a function call.
+#[derive(Debug)]
+pub struct TypedExpr<T> {
+    expr: Expr,
+    _marker: PhantomData<T>,
+}
+
+// Not derived since that would require the SQL type marker to be `Clone`
+impl<T> Clone for TypedExpr<T> {
+    fn clone(&self) -> Self {
+        Self::new(self.expr.clone())
+    }
+}
+
+impl<T> TypedExpr<T> {
+    pub(crate) fn new(expr: Expr) -> Self {
+        Self {
+            expr,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> TypedExpr<T> {
+    /// Select the expression as `alias`.
+    pub fn alias(self, alias: &str) -> Selection {
+        Selection::Expr {
+            expr: self.expr,
+            alias: Some(alias.to_string()),
+        }
+    }
+}
+
+impl<T> IntoExpr for TypedExpr<T> {
+    type SqlType = T;
 
     fn into_expr(self) -> Expr {
-        Expr::I32(self)
+        self.expr
+    }
+}
+
+impl<T> From<TypedExpr<T>> for Selection {
+    fn from(expr: TypedExpr<T>) -> Self {
+        Selection::Expr {
+            expr: expr.expr,
+            alias: None,
+        }
+    }
+}
+
+impl<T> From<TypedExpr<T>> for Select {
+    fn from(expr: TypedExpr<T>) -> Self {
+        Select::Simple(expr.into())
     }
 }
 
-impl IntoExpr for &str {
-    type SqlType = Text;
+pub trait TextExprDsl: Sized {
+    /// `self LIKE pattern`
+    fn like(self, pattern: impl Into<String>) -> Filter;
+
+    /// `self NOT LIKE pattern`
+    fn not_like(self, pattern: impl Into<String>) -> Filter;
+}
+
+impl<T> TextExprDsl for T
+where
+    T: IntoExpr,
+    T::SqlType: CompatibleWith<Text>,
+{
+    fn like(self, pattern: impl Into<String>) -> Filter {
+        Filter::BinOp {
+            lhs: self.into_expr(),
+            op: BinOp::Like,
+            rhs: Expr::Bind(Bind::String(pattern.into())),
+        }
+    }
+
+    fn not_like(self, pattern: impl Into<String>) -> Filter {
+        Filter::BinOp {
+            lhs: self.into_expr(),
+            op: BinOp::NotLike,
+            rhs: Expr::Bind(Bind::String(pattern.into())),
+        }
+    }
+}
+
+macro_rules! impl_into_expr {
+    ($($ty:ty => $sql_type:ident, |$value:ident| $bind:expr;)*) => {
+        $(
+            impl IntoExpr for $ty {
+                type SqlType = $sql_type;
+
+                fn into_expr(self) -> Expr {
+                    Expr::Bind(self.into())
+                }
+            }
+
+            impl From<$ty> for Bind {
+                fn from($value: $ty) -> Self {
+                    $bind
+                }
+            }
+        )*
+    };
+}
+
+impl_into_expr! {
+    i16 => SmallInt, |value| Bind::I16(value);
+    i32 => Integer, |value| Bind::I32(value);
+    i64 => BigInt, |value| Bind::I64(value);
+    bool => Bool, |value| Bind::Bool(value);
+    f32 => Float, |value| Bind::F32(value);
+    f64 => Double, |value| Bind::F64(value);
+    &str => Text, |value| Bind::String(value.to_string());
+    String => Text, |value| Bind::String(value);
+    &[u8] => Bytea, |value| Bind::Bytes(value.to_vec());
+    Vec<u8> => Bytea, |value| Bind::Bytes(value);
+    SystemTime => Timestamptz, |value| Bind::Timestamptz(micros_since_epoch(value));
+}
+
+impl<T: IntoExpr> IntoExpr for Option<T> {
+    type SqlType = Nullable<T::SqlType>;
 
     fn into_expr(self) -> Expr {
-        Expr::String(self.to_string())
+        match self {
+            Some(value) => value.into_expr(),
+            None => Expr::Null,
+        }
     }
 }
 
-impl IntoExpr for String {
-    type SqlType = Text;
+impl<T> IntoExpr for Vec<T>
+where
+    T: IntoExpr + Into<Bind>,
+    T::SqlType: ArrayElement,
+{
+    type SqlType = Array<T::SqlType>;
 
     fn into_expr(self) -> Expr {
-        Expr::String(self)
+        Expr::Bind(Bind::Array {
+            element_type: T::SqlType::NAME,
+            values: self.into_iter().map(Into::into).collect(),
+        })
+    }
+}
+
+// Tuples are row values, such as `("users"."created_at", "users"."id") > ($1, $2)`
+macro_rules! impl_into_expr_for_tuple {
+    ($first:ident,) => {};
+
+    ($head:ident, $($tail:ident,)*) => {
+        #[allow(non_snake_case)]
+        impl<$head, $($tail),*> IntoExpr for ($head, $($tail),*)
+        where
+            $head: IntoExpr,
+            $( $tail: IntoExpr, )*
+        {
+            type SqlType = ($head::SqlType, $($tail::SqlType),*);
+
+            fn into_expr(self) -> Expr {
+                let ($head, $($tail),*) = self;
+                Expr::Tuple(vec![$head.into_expr(), $($tail.into_expr()),*])
+            }
+        }
+
+        impl_into_expr_for_tuple!($($tail,)*);
+    };
+}
+
+impl_into_expr_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12,);
+
+fn micros_since_epoch(time: SystemTime) -> i64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(after) => after.as_micros() as i64,
+        Err(before) => -(before.duration().as_micros() as i64),
     }
 }
 
@@ -152,26 +466,242 @@ pub trait IntoExpr {
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum Expr {
     Column(Column),
-    I32(i32),
-    String(String),
+    Bind(Bind),
+    Null,
+    Raw(String),
+    Function {
+        name: &'static str,
+        args: Vec<Expr>,
+    },
+    BinOp {
+        lhs: Box<Expr>,
+        op: BinOp,
+        rhs: Box<Expr>,
+    },
+    Index {
+        expr: Box<Expr>,
+        index: i32,
+    },
+    Any(Box<Expr>),
+    Cast {
+        expr: Box<Expr>,
+        type_name: &'static str,
+    },
+    Extract {
+        part: DatePart,
+        expr: Box<Expr>,
+    },
+    Tuple(Vec<Expr>),
 }
 
 impl WriteSql for &Expr {
     fn write_sql<W: Write>(self, f: &mut W, bind_count: &mut BindCount) -> fmt::Result {
         match self {
             Expr::Column(col) => col.write_sql(f, bind_count),
-            Expr::I32(_) => bind_count.write_sql(f),
-            Expr::String(_) => bind_count.write_sql(f),
+            Expr::Bind(bind) => {
+                match bind {
+                    Bind::Array { .. } => bind_count.require(Feature::Arrays)?,
+                    Bind::Interval { .. } => bind_count.require(Feature::Intervals)?,
+                    Bind::Jsonb(_) => bind_count.require(Feature::Jsonb)?,
+                    // The cast makes the server check the value against its declared type, which
+                    // drivers can't send without knowing the type's OID
+                    Bind::Custom { type_name, .. }
+                        if bind_count.render_mode(Feature::CustomTypes)? == RenderMode::Native =>
+                    {
+                        write!(f, "CAST(")?;
+                        bind_count.write_sql(f)?;
+                        return write!(f, " AS {})", type_name);
+                    }
+                    _ => {}
+                }
+                bind_count.write_sql(f)
+            }
+            Expr::Null => write!(f, "NULL"),
+            Expr::Raw(sql) => write!(f, "{}", sql),
+            Expr::Function { name, args } => {
+                if let Some(feature) = function_feature(name) {
+                    bind_count.require(feature)?;
+                }
+                write!(f, "{}(", name)?;
+                for (idx, arg) in args.iter().enumerate() {
+                    if idx != 0 {
+                        write!(f, ", ")?;
+                    }
+                    arg.write_sql(f, bind_count)?;
+                }
+                write!(f, ")")
+            }
+            Expr::BinOp { lhs, op, rhs } => {
+                lhs.write_operand(op.precedence(), f, bind_count)?;
+                op.write_sql(f, bind_count)?;
+                rhs.write_operand(op.precedence() + 1, f, bind_count)
+            }
+            Expr::Index { expr, index } => {
+                bind_count.require(Feature::Arrays)?;
+                if let Expr::Column(_) = **expr {
+                    expr.write_sql(f, bind_count)?;
+                } else {
+                    write!(f, "(")?;
+                    expr.write_sql(f, bind_count)?;
+                    write!(f, ")")?;
+                }
+                write!(f, "[{}]", index)
+            }
+            Expr::Any(expr) => {
+                bind_count.require(Feature::Arrays)?;
+                write!(f, "ANY(")?;
+                expr.write_sql(f, bind_count)?;
+                write!(f, ")")
+            }
+            Expr::Cast { expr, type_name } => {
+                write!(f, "CAST(")?;
+                expr.write_sql(f, bind_count)?;
+                write!(f, " AS {})", type_name)
+            }
+            Expr::Extract { part, expr } => {
+                write!(f, "EXTRACT({} FROM ", part.to_string().to_uppercase())?;
+                expr.write_sql(f, bind_count)?;
+                write!(f, ")")
+            }
+            Expr::Tuple(exprs) => {
+                write!(f, "(")?;
+                exprs.iter().write_sql(f, bind_count)?;
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+// Postgres operator precedence, from loosest to tightest. Every operator is left associative, so
+// an operand is parenthesized when it binds looser than its operator, and a right hand side also
+// when it binds the same.
+const COMPARISON: u8 = 0;
+const OTHER_OPERATOR: u8 = 1;
+const ADDITIVE: u8 = 2;
+const AT_TIME_ZONE: u8 = 3;
+const PRIMARY: u8 = 4;
+
+impl Expr {
+    fn precedence(&self) -> u8 {
+        match self {
+            Expr::BinOp { op, .. } => op.precedence(),
+            _ => PRIMARY,
+        }
+    }
+
+    pub(crate) fn write_operand<W: Write>(
+        &self,
+        min_precedence: u8,
+        f: &mut W,
+        bind_count: &mut BindCount,
+    ) -> fmt::Result {
+        if self.precedence() >= min_precedence {
+            self.write_sql(f, bind_count)
+        } else {
+            write!(f, "(")?;
+            self.write_sql(f, bind_count)?;
+            write!(f, ")")
         }
     }
 }
 
+const AGGREGATES: &[&str] = &[
+    "count",
+    "sum",
+    "avg",
+    "min",
+    "max",
+    "array_agg",
+    "string_agg",
+    "json_agg",
+    "jsonb_agg",
+    "bool_and",
+    "bool_or",
+];
+
+impl Expr {
+    // Raw SQL can't be inspected, so it is assumed to contain an aggregate
+    pub(crate) fn may_aggregate(&self) -> bool {
+        match self {
+            Expr::Column(_) | Expr::Bind(_) | Expr::Null => false,
+            Expr::Raw(_) => true,
+            Expr::Function { name, args } => {
+                AGGREGATES.contains(name) || args.iter().any(Expr::may_aggregate)
+            }
+            Expr::BinOp { lhs, op: _, rhs } => lhs.may_aggregate() || rhs.may_aggregate(),
+            Expr::Index { expr, index: _ }
+            | Expr::Any(expr)
+            | Expr::Cast { expr, type_name: _ }
+            | Expr::Extract { part: _, expr } => expr.may_aggregate(),
+            Expr::Tuple(exprs) => exprs.iter().any(Expr::may_aggregate),
+        }
+    }
+}
+
+// The functions the DSL builds that only some dialects have
+fn function_feature(name: &str) -> Option<Feature> {
+    match name {
+        "array_length" => Some(Feature::Arrays),
+        "jsonb_build_object" | "json_agg" | "jsonb_path_exists" => Some(Feature::Jsonb),
+        "to_tsvector" | "plainto_tsquery" | "websearch_to_tsquery" | "ts_rank" => {
+            Some(Feature::FullTextSearch)
+        }
+        "date_trunc" => Some(Feature::DateTrunc),
+        _ => None,
+    }
+}
+
 impl CollectBinds for Expr {
     fn collect_binds(&self, binds: &mut BindsInternal) {
         match self {
             Expr::Column(_) => {}
-            Expr::I32(value) => binds.push(Bind::I32(*value)),
-            Expr::String(value) => binds.push(Bind::String(value.clone())),
+            Expr::Bind(bind) => binds.push(bind.clone()),
+            Expr::Null | Expr::Raw(_) => {}
+            Expr::Function { name: _, args } => {
+                for arg in args {
+                    arg.collect_binds(binds);
+                }
+            }
+            Expr::BinOp { lhs, op: _, rhs } => {
+                lhs.collect_binds(binds);
+                rhs.collect_binds(binds);
+            }
+            Expr::Index { expr, index: _ }
+            | Expr::Any(expr)
+            | Expr::Cast { expr, type_name: _ }
+            | Expr::Extract { part: _, expr } => expr.collect_binds(binds),
+            Expr::Tuple(exprs) => {
+                for expr in exprs {
+                    expr.collect_binds(binds);
+                }
+            }
+        }
+    }
+}
+
+impl CollectTableRefs for Expr {
+    fn collect_table_refs<'a>(&'a self, refs: &mut Vec<TableRef<'a>>) {
+        match self {
+            Expr::Column(col) => refs.push(TableRef::Column(col)),
+            Expr::Bind(_) | Expr::Null | Expr::Raw(_) => {}
+            Expr::Function { name: _, args } => {
+                for arg in args {
+                    arg.collect_table_refs(refs);
+                }
+            }
+            Expr::BinOp { lhs, op: _, rhs } => {
+                lhs.collect_table_refs(refs);
+                rhs.collect_table_refs(refs);
+            }
+            Expr::Index { expr, index: _ }
+            | Expr::Any(expr)
+            | Expr::Cast { expr, type_name: _ }
+            | Expr::Extract { part: _, expr } => expr.collect_table_refs(refs),
+            Expr::Tuple(exprs) => {
+                for expr in exprs {
+                    expr.collect_table_refs(refs);
+                }
+            }
         }
     }
 }
@@ -184,10 +714,39 @@ pub enum BinOp {
     Gt,
     Lt,
     Le,
+    Contains,
+    JsonContains,
+    ContainedBy,
+    Overlaps,
+    Like,
+    NotLike,
+    JsonGet,
+    JsonGetText,
+    JsonGetPath,
+    JsonGetPathText,
+    JsonHasKey,
+    JsonHasAnyKey,
+    JsonHasAllKeys,
+    TsMatch,
+    Add,
+    Sub,
+    AtTimeZone,
 }
 
 impl WriteSql for &BinOp {
-    fn write_sql<W: Write>(self, f: &mut W, _: &mut BindCount) -> fmt::Result {
+    fn write_sql<W: Write>(self, f: &mut W, bind_count: &mut BindCount) -> fmt::Result {
+        if self.is_json() {
+            bind_count.require(Feature::Jsonb)?;
+        }
+        match self {
+            BinOp::Contains | BinOp::ContainedBy | BinOp::Overlaps => {
+                bind_count.require(Feature::Arrays)?
+            }
+            BinOp::TsMatch => bind_count.require(Feature::FullTextSearch)?,
+            BinOp::AtTimeZone => bind_count.require(Feature::AtTimeZone)?,
+            _ => {}
+        }
+
         match self {
             BinOp::Eq => write!(f, " = "),
             BinOp::Ne => write!(f, " != "),
@@ -195,8 +754,67 @@ impl WriteSql for &BinOp {
             BinOp::Gt => write!(f, " > "),
             BinOp::Lt => write!(f, " < "),
             BinOp::Le => write!(f, " <= "),
+            BinOp::Contains | BinOp::JsonContains => write!(f, " @> "),
+            BinOp::ContainedBy => write!(f, " <@ "),
+            BinOp::Overlaps => write!(f, " && "),
+            BinOp::Like => write!(f, " LIKE "),
+            BinOp::NotLike => write!(f, " NOT LIKE "),
+            BinOp::JsonGet => write!(f, " -> "),
+            BinOp::JsonGetText => write!(f, " ->> "),
+            BinOp::JsonGetPath => write!(f, " #> "),
+            BinOp::JsonGetPathText => write!(f, " #>> "),
+            BinOp::JsonHasKey => write!(f, " ? "),
+            BinOp::JsonHasAnyKey => write!(f, " ?| "),
+            BinOp::JsonHasAllKeys => write!(f, " ?& "),
+            BinOp::TsMatch => write!(f, " @@ "),
+            BinOp::Add => write!(f, " + "),
+            BinOp::Sub => write!(f, " - "),
+            BinOp::AtTimeZone => write!(f, " AT TIME ZONE "),
+        }
+    }
+}
+
+impl BinOp {
+    pub(crate) fn precedence(self) -> u8 {
+        match self {
+            BinOp::Eq
+            | BinOp::Ne
+            | BinOp::Ge
+            | BinOp::Gt
+            | BinOp::Lt
+            | BinOp::Le
+            | BinOp::Like
+            | BinOp::NotLike => COMPARISON,
+            BinOp::Contains
+            | BinOp::JsonContains
+            | BinOp::ContainedBy
+            | BinOp::Overlaps
+            | BinOp::JsonGet
+            | BinOp::JsonGetText
+            | BinOp::JsonGetPath
+            | BinOp::JsonGetPathText
+            | BinOp::JsonHasKey
+            | BinOp::JsonHasAnyKey
+            | BinOp::JsonHasAllKeys
+            | BinOp::TsMatch => OTHER_OPERATOR,
+            BinOp::Add | BinOp::Sub => ADDITIVE,
+            BinOp::AtTimeZone => AT_TIME_ZONE,
         }
     }
+
+    fn is_json(self) -> bool {
+        matches!(
+            self,
+            BinOp::JsonContains
+                | BinOp::JsonGet
+                | BinOp::JsonGetText
+                | BinOp::JsonGetPath
+                | BinOp::JsonGetPathText
+                | BinOp::JsonHasKey
+                | BinOp::JsonHasAnyKey
+                | BinOp::JsonHasAllKeys
+        )
+    }
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
@@ -0,0 +1,117 @@
+// `IntoExpr` and `Bind` conversions for types from other crates, each behind a feature of the same
+// name.
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use crate::sql_types::{Date, Timestamp, Timestamptz};
+    use crate::{Bind, Expr, IntoExpr};
+    use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+
+    impl From<NaiveDate> for Bind {
+        fn from(date: NaiveDate) -> Self {
+            let days = date
+                .signed_duration_since(DateTime::UNIX_EPOCH.date_naive())
+                .num_days();
+            Bind::Date(days as i32)
+        }
+    }
+
+    impl From<NaiveDateTime> for Bind {
+        fn from(timestamp: NaiveDateTime) -> Self {
+            Bind::Timestamp(timestamp.and_utc().timestamp_micros())
+        }
+    }
+
+    impl From<DateTime<Utc>> for Bind {
+        fn from(timestamp: DateTime<Utc>) -> Self {
+            Bind::Timestamptz(timestamp.timestamp_micros())
+        }
+    }
+
+    impl IntoExpr for NaiveDate {
+        type SqlType = Date;
+
+        fn into_expr(self) -> Expr {
+            Expr::Bind(self.into())
+        }
+    }
+
+    impl IntoExpr for NaiveDateTime {
+        type SqlType = Timestamp;
+
+        fn into_expr(self) -> Expr {
+            Expr::Bind(self.into())
+        }
+    }
+
+    impl IntoExpr for DateTime<Utc> {
+        type SqlType = Timestamptz;
+
+        fn into_expr(self) -> Expr {
+            Expr::Bind(self.into())
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_impls {
+    use crate::sql_types::Timestamptz;
+    use crate::{Bind, Expr, IntoExpr};
+    use time::OffsetDateTime;
+
+    impl From<OffsetDateTime> for Bind {
+        fn from(timestamp: OffsetDateTime) -> Self {
+            let micros = timestamp.unix_timestamp_nanos().div_euclid(1_000);
+            Bind::Timestamptz(micros as i64)
+        }
+    }
+
+    impl IntoExpr for OffsetDateTime {
+        type SqlType = Timestamptz;
+
+        fn into_expr(self) -> Expr {
+            Expr::Bind(self.into())
+        }
+    }
+}
+
+#[cfg(feature = "uuid")]
+mod uuid_impls {
+    use crate::sql_types::Uuid;
+    use crate::{Bind, Expr, IntoExpr};
+
+    impl From<uuid::Uuid> for Bind {
+        fn from(uuid: uuid::Uuid) -> Self {
+            Bind::Uuid(uuid.into_bytes())
+        }
+    }
+
+    impl IntoExpr for uuid::Uuid {
+        type SqlType = Uuid;
+
+        fn into_expr(self) -> Expr {
+            Expr::Bind(self.into())
+        }
+    }
+}
+
+#[cfg(feature = "serde_json")]
+mod serde_json_impls {
+    use crate::sql_types::Jsonb;
+    use crate::{Bind, Expr, IntoExpr};
+
+    impl From<serde_json::Value> for Bind {
+        fn from(value: serde_json::Value) -> Self {
+            Bind::Jsonb(value.to_string())
+        }
+    }
+
+    // Use `Value::json` to compare with `json` columns.
+    impl IntoExpr for serde_json::Value {
+        type SqlType = Jsonb;
+
+        fn into_expr(self) -> Expr {
+            Expr::Bind(self.into())
+        }
+    }
+}
@@ -1,40 +1,106 @@
 use crate::binds::BindCount;
 use crate::binds::{BindsInternal, CollectBinds};
-use crate::{expr::Expr, BinOp, UnOp, WriteSql};
+use crate::dialect::RenderMode;
+use crate::validate::{CollectTableRefs, TableRef};
+use crate::{expr::Expr, BinOp, Feature, UnOp, WriteSql};
 use std::fmt::{self, Write};
+use std::ops;
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum Filter {
     BinOp { lhs: Expr, op: BinOp, rhs: Expr },
     UnOp { expr: Expr, op: UnOp },
+    Expr(Expr),
     And(Box<Filter>, Box<Filter>),
     Or(Box<Filter>, Box<Filter>),
+    Not(Box<Filter>),
+    In { lhs: Expr, values: Vec<Expr> },
+    True,
+    False,
     Raw(String),
 }
 
+// Operands are parenthesized when they bind looser than their operator. Raw SQL could contain
+// anything so it is always parenthesized when nested.
+const RAW: u8 = 0;
+const OR: u8 = 1;
+const AND: u8 = 2;
+const NOT: u8 = 3;
+const COMPARISON: u8 = 4;
+const PRIMARY: u8 = 5;
+
+impl Filter {
+    fn precedence(&self) -> u8 {
+        match self {
+            Filter::Raw(_) => RAW,
+            Filter::Or(_, _) => OR,
+            Filter::And(_, _) => AND,
+            Filter::Not(_) => NOT,
+            Filter::BinOp { .. } | Filter::UnOp { .. } | Filter::In { .. } => COMPARISON,
+            Filter::Expr(_) | Filter::True | Filter::False => PRIMARY,
+        }
+    }
+
+    fn write_operand<W: Write>(
+        &self,
+        min_precedence: u8,
+        f: &mut W,
+        bind_count: &mut BindCount,
+    ) -> fmt::Result {
+        if self.precedence() >= min_precedence && self.precedence() != RAW {
+            self.write_sql(f, bind_count)
+        } else {
+            write!(f, "(")?;
+            self.write_sql(f, bind_count)?;
+            write!(f, ")")
+        }
+    }
+}
+
 impl WriteSql for &Filter {
     fn write_sql<W: Write>(self, f: &mut W, bind_count: &mut BindCount) -> fmt::Result {
         match self {
             Filter::BinOp { lhs, op, rhs } => {
-                lhs.write_sql(f, bind_count)?;
+                lhs.write_operand(op.precedence(), f, bind_count)?;
                 op.write_sql(f, bind_count)?;
-                rhs.write_sql(f, bind_count)?;
+                rhs.write_operand(op.precedence() + 1, f, bind_count)?;
             }
             Filter::UnOp { expr, op } => {
                 expr.write_sql(f, bind_count)?;
                 op.write_sql(f, bind_count)?;
             }
+            Filter::Expr(expr) => {
+                expr.write_sql(f, bind_count)?;
+            }
             Filter::And(lhs, rhs) => {
-                lhs.write_sql(f, bind_count)?;
+                lhs.write_operand(AND, f, bind_count)?;
                 write!(f, " AND ")?;
-                rhs.write_sql(f, bind_count)?;
+                rhs.write_operand(AND, f, bind_count)?;
             }
             Filter::Or(lhs, rhs) => {
-                write!(f, "(")?;
+                lhs.write_operand(OR, f, bind_count)?;
+                write!(f, " OR ")?;
+                rhs.write_operand(OR, f, bind_count)?;
+            }
+            // `NOT` binds looser than comparisons in standard SQL, but MySQL's
+            // `HIGH_NOT_PRECEDENCE` mode flips that, so comparisons are parenthesized too
+            Filter::Not(inner) => {
+                write!(f, "NOT ")?;
+                inner.write_operand(PRIMARY, f, bind_count)?;
+            }
+            Filter::In { lhs, values } => {
                 lhs.write_sql(f, bind_count)?;
-                write!(f, ") OR ")?;
-                rhs.write_sql(f, bind_count)?;
+                write!(f, " IN (")?;
+                if let Expr::Tuple(_) = lhs {
+                    if bind_count.render_mode(Feature::RowValueInList)? == RenderMode::Emulated {
+                        write!(f, "VALUES ")?;
+                    }
+                }
+                values.iter().write_sql(f, bind_count)?;
+                write!(f, ")")?;
             }
+            Filter::True => write!(f, "TRUE")?,
+            Filter::False => write!(f, "FALSE")?,
             Filter::Raw(sql) => {
                 write!(f, "{}", sql)?;
             }
@@ -49,6 +115,40 @@ impl Filter {
         Filter::Raw(sql.to_string())
     }
 
+    pub fn true_() -> Self {
+        Filter::True
+    }
+
+    pub fn false_() -> Self {
+        Filter::False
+    }
+
+    /// All of `filters` ANDed together, or `TRUE` if there are none.
+    pub fn all<I>(filters: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Filter>,
+    {
+        filters
+            .into_iter()
+            .map(Into::into)
+            .reduce(Filter::and)
+            .unwrap_or(Filter::True)
+    }
+
+    /// Any of `filters` ORed together, or `FALSE` if there are none.
+    pub fn any<I>(filters: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Filter>,
+    {
+        filters
+            .into_iter()
+            .map(Into::into)
+            .reduce(Filter::or)
+            .unwrap_or(Filter::False)
+    }
+
     pub fn and(self, rhs: Filter) -> Self {
         Filter::And(Box::new(self), Box::new(rhs))
     }
@@ -56,6 +156,19 @@ impl Filter {
     pub fn or(self, rhs: Filter) -> Self {
         Filter::Or(Box::new(self), Box::new(rhs))
     }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Self {
+        Filter::Not(Box::new(self))
+    }
+}
+
+impl ops::Not for Filter {
+    type Output = Filter;
+
+    fn not(self) -> Filter {
+        Filter::not(self)
+    }
 }
 
 impl CollectBinds for Filter {
@@ -65,7 +178,7 @@ impl CollectBinds for Filter {
                 lhs.collect_binds(binds);
                 rhs.collect_binds(binds);
             }
-            Filter::UnOp { expr, op: _ } => {
+            Filter::UnOp { expr, op: _ } | Filter::Expr(expr) => {
                 expr.collect_binds(binds);
             }
             Filter::And(lhs, rhs) => {
@@ -76,7 +189,44 @@ impl CollectBinds for Filter {
                 lhs.collect_binds(binds);
                 rhs.collect_binds(binds);
             }
-            Filter::Raw(_) => {}
+            Filter::Not(inner) => inner.collect_binds(binds),
+            Filter::In { lhs, values } => {
+                lhs.collect_binds(binds);
+                for value in values {
+                    value.collect_binds(binds);
+                }
+            }
+            Filter::True | Filter::False | Filter::Raw(_) => {}
+        }
+    }
+}
+
+impl CollectTableRefs for Filter {
+    fn collect_table_refs<'a>(&'a self, refs: &mut Vec<TableRef<'a>>) {
+        match self {
+            Filter::BinOp { lhs, op: _, rhs } => {
+                lhs.collect_table_refs(refs);
+                rhs.collect_table_refs(refs);
+            }
+            Filter::UnOp { expr, op: _ } | Filter::Expr(expr) => {
+                expr.collect_table_refs(refs);
+            }
+            Filter::And(lhs, rhs) => {
+                lhs.collect_table_refs(refs);
+                rhs.collect_table_refs(refs);
+            }
+            Filter::Or(lhs, rhs) => {
+                lhs.collect_table_refs(refs);
+                rhs.collect_table_refs(refs);
+            }
+            Filter::Not(inner) => inner.collect_table_refs(refs),
+            Filter::In { lhs, values } => {
+                lhs.collect_table_refs(refs);
+                for value in values {
+                    value.collect_table_refs(refs);
+                }
+            }
+            Filter::True | Filter::False | Filter::Raw(_) => {}
         }
     }
 }
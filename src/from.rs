@@ -1,7 +1,9 @@
 use crate::binds::{BindCount, BindsInternal, CollectBinds};
 use crate::write_sql::WriteSql;
+use crate::Error;
 use crate::Query;
 use crate::QueryWithSelect;
+use crate::SetOperation;
 use crate::Table;
 use extend::ext;
 use std::fmt::{self, Write};
@@ -21,6 +23,39 @@ impl<T> FromClause<T> {
     }
 }
 
+impl<T> FromClause<T> {
+    pub(crate) fn check(&self) -> Result<(), Error> {
+        match self {
+            FromClause::Table(_) => Ok(()),
+            FromClause::SubQuery(sub_query) => sub_query.query.check(),
+        }
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), Error> {
+        match self {
+            FromClause::Table(_) => Ok(()),
+            FromClause::SubQuery(sub_query) => sub_query.query.validate(),
+        }
+    }
+
+    pub(crate) fn name(&self) -> &str {
+        match self {
+            FromClause::Table(table) => table.name(),
+            FromClause::SubQuery(sub_query) => &sub_query.alias,
+        }
+    }
+}
+
+impl<T> PartialEq for FromClause<T> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (FromClause::Table(lhs), FromClause::Table(rhs)) => lhs == rhs,
+            (FromClause::SubQuery(lhs), FromClause::SubQuery(rhs)) => lhs == rhs,
+            _ => false,
+        }
+    }
+}
+
 impl<T> From<Table> for FromClause<T> {
     fn from(table: Table) -> Self {
         FromClause::Table(table)
@@ -56,9 +91,15 @@ pub trait IntoSubQuery<T> {
 }
 
 impl<T> IntoSubQuery<T> for QueryWithSelect<T> {
+    fn alias(self, alias: &str) -> SubQuery<T> {
+        SetOperation::from(self).alias(alias)
+    }
+}
+
+impl<T> IntoSubQuery<T> for SetOperation<T> {
     fn alias(self, alias: &str) -> SubQuery<T> {
         SubQuery {
-            query: Box::new(self.into()),
+            query: Box::new(self),
             alias: alias.to_string(),
         }
     }
@@ -66,7 +107,7 @@ impl<T> IntoSubQuery<T> for QueryWithSelect<T> {
 
 #[derive(Debug, Clone)]
 pub struct SubQuery<T> {
-    pub(crate) query: Box<QueryWithSelect<T>>,
+    pub(crate) query: Box<SetOperation<T>>,
     pub(crate) alias: String,
 }
 
@@ -81,6 +122,12 @@ impl<T> SubQuery<T> {
     }
 }
 
+impl<T> PartialEq for SubQuery<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.alias == other.alias && self.query == other.query
+    }
+}
+
 impl<T> CollectBinds for SubQuery<T> {
     fn collect_binds(&self, binds: &mut BindsInternal) {
         self.query.collect_binds(binds)
@@ -100,7 +147,8 @@ impl<T> WriteSql for &SubQuery<T> {
     fn write_sql<W: Write>(self, f: &mut W, bind_count: &mut BindCount) -> fmt::Result {
         write!(f, "(")?;
         self.query.write_sql(f, bind_count)?;
-        write!(f, ") \"{}\"", self.alias)?;
+        write!(f, ") ")?;
+        bind_count.write_identifier(f, &self.alias)?;
         Ok(())
     }
 }
@@ -1,9 +1,10 @@
 use crate::binds::BindCount;
 use crate::binds::{BindsInternal, CollectBinds};
+use crate::validate::{CollectTableRefs, TableRef};
 use crate::{Column, WriteSql};
 use std::fmt::{self, Write};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Group {
     Col(Column),
     And { lhs: Box<Group>, rhs: Box<Group> },
@@ -44,12 +45,25 @@ impl CollectBinds for Group {
     fn collect_binds(&self, _: &mut BindsInternal) {}
 }
 
-impl<T> Into<Group> for (T,)
+impl CollectTableRefs for Group {
+    fn collect_table_refs<'a>(&'a self, refs: &mut Vec<TableRef<'a>>) {
+        match self {
+            Group::Col(col) => refs.push(TableRef::Column(col)),
+            Group::And { lhs, rhs } => {
+                lhs.collect_table_refs(refs);
+                rhs.collect_table_refs(refs);
+            }
+            Group::Raw(_) => {}
+        }
+    }
+}
+
+impl<T> From<(T,)> for Group
 where
     T: Into<Group>,
 {
-    fn into(self) -> Group {
-        self.0.into()
+    fn from(group: (T,)) -> Self {
+        group.0.into()
     }
 }
 
@@ -1,7 +1,7 @@
 use crate::binds::BindCount;
 use crate::binds::{BindsInternal, CollectBinds};
 use crate::from::FromClause;
-use crate::{filter::Filter, WriteSql};
+use crate::{filter::Filter, Error, WriteSql};
 use extend::ext;
 use std::fmt::{self, Write};
 
@@ -21,6 +21,13 @@ impl<T> Join<T> {
         JoinOn::Raw(sql.to_string())
     }
 
+    pub(crate) fn check(&self) -> Result<(), Error> {
+        match self {
+            Join::Known { from, .. } => from.check(),
+            Join::RawWithKind(_) | Join::Raw(_) => Ok(()),
+        }
+    }
+
     pub fn cast_to<K>(self) -> Join<K> {
         match self {
             Join::Known { kind, from, filter } => Join::Known {
@@ -34,6 +41,24 @@ impl<T> Join<T> {
     }
 }
 
+impl<T> PartialEq for Join<T> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                Join::Known { kind, from, filter },
+                Join::Known {
+                    kind: other_kind,
+                    from: other_from,
+                    filter: other_filter,
+                },
+            ) => kind == other_kind && from == other_from && filter == other_filter,
+            (Join::RawWithKind(lhs), Join::RawWithKind(rhs)) => lhs == rhs,
+            (Join::Raw(lhs), Join::Raw(rhs)) => lhs == rhs,
+            _ => false,
+        }
+    }
+}
+
 impl<T> WriteSql for &Join<T> {
     fn write_sql<W: Write>(self, f: &mut W, bind_count: &mut BindCount) -> fmt::Result {
         match self {
@@ -80,7 +105,7 @@ impl<T> CollectBinds for Vec<Join<T>> {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub enum JoinKind {
     Default,
     Inner,
@@ -126,7 +151,7 @@ where
 {
     fn on(self, filter: Filter) -> JoinOn<K> {
         JoinOn::Known {
-            from: FromClause::from(self.into()),
+            from: self.into(),
             filter,
         }
     }
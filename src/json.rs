@@ -0,0 +1,164 @@
+use crate::sql_types::{Json, Jsonb, Text};
+use crate::{BinOp, Expr, Filter, IntoExpr, Select, Selection, TypedExpr};
+
+/// Postgres `jsonb` operators and functions.
+pub trait JsonbExprDsl: Sized {
+    /// `self -> key`
+    fn get(self, key: &str) -> TypedExpr<Jsonb>;
+
+    /// `self -> index`, where negative indices count from the end.
+    fn get_index(self, index: i32) -> TypedExpr<Jsonb>;
+
+    /// `self ->> key`
+    fn get_text(self, key: &str) -> TypedExpr<Text>;
+
+    /// `self #> path`
+    fn get_path(self, path: &[&str]) -> TypedExpr<Jsonb>;
+
+    /// `self #>> path`
+    fn get_path_text(self, path: &[&str]) -> TypedExpr<Text>;
+
+    /// `self @> rhs`
+    fn contains<Rhs>(self, rhs: Rhs) -> Filter
+    where
+        Rhs: IntoExpr<SqlType = Jsonb>;
+
+    /// `self ? key`
+    fn has_key(self, key: &str) -> Filter;
+
+    /// `self ?| keys`
+    fn has_any_key(self, keys: &[&str]) -> Filter;
+
+    /// `self ?& keys`
+    fn has_all_keys(self, keys: &[&str]) -> Filter;
+
+    /// `jsonb_path_exists(self, path)`
+    fn path_exists(self, path: &str) -> Filter;
+}
+
+impl<T> JsonbExprDsl for T
+where
+    T: IntoExpr<SqlType = Jsonb>,
+{
+    fn get(self, key: &str) -> TypedExpr<Jsonb> {
+        json_op(self, BinOp::JsonGet, key.into_expr())
+    }
+
+    fn get_index(self, index: i32) -> TypedExpr<Jsonb> {
+        json_op(self, BinOp::JsonGet, index.into_expr())
+    }
+
+    fn get_text(self, key: &str) -> TypedExpr<Text> {
+        json_op(self, BinOp::JsonGetText, key.into_expr())
+    }
+
+    fn get_path(self, path: &[&str]) -> TypedExpr<Jsonb> {
+        json_op(self, BinOp::JsonGetPath, path.to_vec().into_expr())
+    }
+
+    fn get_path_text(self, path: &[&str]) -> TypedExpr<Text> {
+        json_op(self, BinOp::JsonGetPathText, path.to_vec().into_expr())
+    }
+
+    fn contains<Rhs>(self, rhs: Rhs) -> Filter
+    where
+        Rhs: IntoExpr<SqlType = Jsonb>,
+    {
+        Filter::BinOp {
+            lhs: self.into_expr(),
+            op: BinOp::JsonContains,
+            rhs: rhs.into_expr(),
+        }
+    }
+
+    fn has_key(self, key: &str) -> Filter {
+        Filter::BinOp {
+            lhs: self.into_expr(),
+            op: BinOp::JsonHasKey,
+            rhs: key.into_expr(),
+        }
+    }
+
+    fn has_any_key(self, keys: &[&str]) -> Filter {
+        Filter::BinOp {
+            lhs: self.into_expr(),
+            op: BinOp::JsonHasAnyKey,
+            rhs: keys.to_vec().into_expr(),
+        }
+    }
+
+    fn has_all_keys(self, keys: &[&str]) -> Filter {
+        Filter::BinOp {
+            lhs: self.into_expr(),
+            op: BinOp::JsonHasAllKeys,
+            rhs: keys.to_vec().into_expr(),
+        }
+    }
+
+    fn path_exists(self, path: &str) -> Filter {
+        Filter::Expr(Expr::Function {
+            name: "jsonb_path_exists",
+            args: vec![self.into_expr(), path.into_expr()],
+        })
+    }
+}
+
+fn json_op<T, Lhs: IntoExpr>(lhs: Lhs, op: BinOp, rhs: Expr) -> TypedExpr<T> {
+    TypedExpr::new(Expr::BinOp {
+        lhs: Box::new(lhs.into_expr()),
+        op,
+        rhs: Box::new(rhs),
+    })
+}
+
+fn function<T>(name: &'static str, args: Vec<Expr>) -> TypedExpr<T> {
+    TypedExpr::new(Expr::Function { name, args })
+}
+
+/// `jsonb_build_object(key, value, ...)`. Add the pairs with `field`.
+pub fn jsonb_build_object() -> JsonbBuildObject {
+    JsonbBuildObject { args: Vec::new() }
+}
+
+#[derive(Debug, Clone)]
+pub struct JsonbBuildObject {
+    args: Vec<Expr>,
+}
+
+impl JsonbBuildObject {
+    pub fn field(mut self, key: &str, value: impl IntoExpr) -> Self {
+        self.args.push(key.into_expr());
+        self.args.push(value.into_expr());
+        self
+    }
+
+    /// Select the object as `alias`.
+    pub fn alias(self, alias: &str) -> Selection {
+        TypedExpr::<Jsonb>::new(self.into_expr()).alias(alias)
+    }
+}
+
+impl IntoExpr for JsonbBuildObject {
+    type SqlType = Jsonb;
+
+    fn into_expr(self) -> Expr {
+        function::<Jsonb>("jsonb_build_object", self.args).into_expr()
+    }
+}
+
+impl From<JsonbBuildObject> for Selection {
+    fn from(object: JsonbBuildObject) -> Self {
+        TypedExpr::<Jsonb>::new(object.into_expr()).into()
+    }
+}
+
+impl From<JsonbBuildObject> for Select {
+    fn from(object: JsonbBuildObject) -> Self {
+        Select::Simple(object.into())
+    }
+}
+
+/// `json_agg(expr)`, which aggregates the rows into a JSON array.
+pub fn json_agg(expr: impl IntoExpr) -> TypedExpr<Json> {
+    function("json_agg", vec![expr.into_expr()])
+}
@@ -1,7 +1,12 @@
 #![forbid(unknown_lints)]
 
+// Lets the derives refer to `::dilemma` from inside this crate as well
+extern crate self as dilemma;
+
 use binds::{BindCount, BindsInternal, CollectBinds};
+use dialect::RenderMode;
 use join::CastVecJoin;
+use order::Ordering;
 use row_locking::RowLocking;
 use std::fmt;
 use std::fmt::Write;
@@ -14,36 +19,83 @@ mod macros;
 mod test;
 
 mod binds;
+mod count;
 mod cte;
+mod custom_type;
+#[cfg(any(feature = "rusqlite", feature = "sqlx-mysql", feature = "sqlx-sqlite"))]
+mod datetime;
+mod dialect;
 mod distinct;
+mod error;
 mod expr;
+#[cfg(any(
+    feature = "chrono",
+    feature = "time",
+    feature = "uuid",
+    feature = "serde_json"
+))]
+mod external_types;
 mod filter;
 mod from;
 mod group;
 mod join;
+mod json;
 mod limit;
+mod merge;
 mod offset;
 mod order;
+mod pagination;
+#[cfg(any(feature = "postgres-types", feature = "sqlx-postgres"))]
+mod pg_encoding;
+#[cfg(feature = "postgres-types")]
+mod postgres;
 mod query_dsl;
 mod row_locking;
+mod search;
 mod select;
+mod set_operation;
+mod simplify;
+#[cfg(feature = "rusqlite")]
+mod sqlite;
+#[cfg(feature = "sqlx")]
+mod sqlx_query;
+mod temporal;
+mod validate;
+mod value;
 mod write_sql;
 
 pub mod sql_types;
 
 pub use binds::{Bind, Binds};
 pub use cte::Ctes;
+pub use custom_type::CustomType;
+pub use dialect::{Dialect, Feature, MySql, Postgres, Sqlite};
+pub use dilemma_derive::SqlEnum;
 pub use distinct::Distinct;
-pub use expr::{BinOp, Expr, ExprDsl, IntoExpr, UnOp};
+pub use error::Error;
+pub use expr::{
+    ArrayExprDsl, BinOp, Expr, ExprDsl, IntoExpr, NullableExprDsl, TextExprDsl, TypedExpr, UnOp,
+};
 pub use filter::Filter;
 pub use from::{from, FromClause, IntoSubQuery, SubQuery};
 pub use group::Group;
 pub use join::{Join, JoinKind, JoinOn, JoinOnDsl};
+pub use json::{json_agg, jsonb_build_object, JsonbBuildObject, JsonbExprDsl};
 pub use limit::Limit;
+pub use merge::{MergeOptions, MergePolicy};
 pub use offset::Offset;
 pub use order::{NullsPosition, NullsPositionDsl, Order, OrderDsl};
+pub use pagination::Cursor;
 pub use query_dsl::QueryDsl;
+pub use search::{plainto_tsquery, to_tsvector, ts_rank, websearch_to_tsquery, TsVectorExprDsl};
 pub use select::{count, star, Select, Selection};
+pub use set_operation::{SetOperation, SetOperator};
+#[cfg(feature = "rusqlite")]
+pub use sqlite::SqliteError;
+#[cfg(feature = "sqlx")]
+pub use sqlx_query::SqlxDatabase;
+pub use temporal::{current_date, date_trunc, extract, now, DatePart, DateTimeExprDsl, Interval};
+pub use value::Value;
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct Table {
@@ -51,8 +103,8 @@ pub struct Table {
 }
 
 impl WriteSql for &Table {
-    fn write_sql<W: Write>(self, f: &mut W, _: &mut BindCount) -> fmt::Result {
-        write!(f, "\"{}\"", self.name)
+    fn write_sql<W: Write>(self, f: &mut W, bind_count: &mut BindCount) -> fmt::Result {
+        bind_count.write_identifier(f, self.name)
     }
 }
 
@@ -62,7 +114,7 @@ impl Table {
     }
 
     pub fn name(&self) -> &'static str {
-        &self.name
+        self.name
     }
 }
 
@@ -79,8 +131,10 @@ impl Column {
 }
 
 impl WriteSql for &Column {
-    fn write_sql<W: Write>(self, f: &mut W, _: &mut BindCount) -> fmt::Result {
-        write!(f, "\"{}\".\"{}\"", self.table, self.name)
+    fn write_sql<W: Write>(self, f: &mut W, bind_count: &mut BindCount) -> fmt::Result {
+        bind_count.write_identifier(f, self.table)?;
+        write!(f, ".")?;
+        bind_count.write_identifier(f, self.name)
     }
 }
 
@@ -101,6 +155,40 @@ pub struct Query<T> {
     _marker: PhantomData<T>,
 }
 
+// Implemented by hand since deriving would require `T: PartialEq`
+impl<T> PartialEq for Query<T> {
+    fn eq(&self, other: &Self) -> bool {
+        let Query {
+            ctes,
+            from,
+            joins,
+            filter,
+            group,
+            having,
+            order,
+            limit,
+            offset,
+            row_locking,
+            distinct,
+            explain,
+            _marker,
+        } = self;
+
+        *ctes == other.ctes
+            && *from == other.from
+            && *joins == other.joins
+            && *filter == other.filter
+            && *group == other.group
+            && *having == other.having
+            && *order == other.order
+            && *limit == other.limit
+            && *offset == other.offset
+            && *row_locking == other.row_locking
+            && *distinct == other.distinct
+            && *explain == other.explain
+    }
+}
+
 impl<T> Query<T> {
     pub fn cast_to<K>(self) -> Query<K> {
         let Query {
@@ -146,6 +234,13 @@ impl<T> Query<T> {
         self
     }
 
+    /// Simplifies the `WHERE` and `HAVING` filters with `Filter::simplify`.
+    pub fn simplify_filters(mut self) -> Self {
+        self.filter = self.filter.map(Filter::simplify);
+        self.having = self.having.map(Filter::simplify);
+        self
+    }
+
     pub fn remove_group_by(mut self) -> Self {
         self.group = None;
         self
@@ -257,81 +352,237 @@ pub struct QueryWithSelect<T> {
     selection: Select,
 }
 
+impl<T> PartialEq for QueryWithSelect<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.query == other.query && self.selection == other.selection
+    }
+}
+
 impl<T> QueryWithSelect<T> {
     pub fn to_sql(self) -> (String, Binds) {
-        let mut bind_count = BindCount::new();
+        self.render(&Postgres)
+            .expect("Postgres supports everything and writing to a String never fails")
+    }
+
+    pub fn try_to_sql(self) -> Result<(String, Binds), Error> {
+        self.to_sql_with(&Postgres)
+    }
+
+    pub fn to_sql_with(self, dialect: &dyn Dialect) -> Result<(String, Binds), Error> {
+        self.check()?;
+        let (sql, binds) = self.render(dialect)?;
+        check_bind_count(&binds, dialect)?;
+        Ok((sql, binds))
+    }
+
+    fn render(&self, dialect: &dyn Dialect) -> Result<(String, Binds), Error> {
+        let mut bind_count = BindCount::new(dialect);
         let mut sql = String::new();
-        self.to_sql_string(&mut sql, &mut bind_count);
+        if let Err(err) = self.to_sql_string(&mut sql, &mut bind_count) {
+            return Err(bind_count.into_error(err));
+        }
 
-        let mut binds = BindsInternal::with_capacity(bind_count.count());
+        let mut binds = BindsInternal::with_capacity(bind_count.count(), dialect);
         self.collect_binds(&mut binds);
-        (sql, Binds::from(binds))
+        Ok((sql, Binds::from(binds)))
     }
 
-    fn to_sql_string<W: Write>(&self, f: &mut W, bind_count: &mut BindCount) {
-        let result = (|| -> fmt::Result {
-            if let Some(explain) = self.query.explain {
-                explain.write_sql(f, bind_count)?;
-            }
+    pub(crate) fn check(&self) -> Result<(), Error> {
+        if self.selection.is_empty() {
+            return Err(Error::EmptySelect);
+        }
 
-            self.query.ctes.write_sql(f, bind_count)?;
+        if let (Some(distinct), Some(order)) = (&self.query.distinct, &self.query.order) {
+            distinct.check(order)?;
+        }
 
-            write!(f, "SELECT ")?;
+        self.query.row_locking.check()?;
 
-            if let Some(distinct) = &self.query.distinct {
-                distinct.write_sql(f, bind_count)?;
-            }
+        self.query.ctes.check()?;
+        self.query.from.check()?;
+        for join in &self.query.joins {
+            join.check()?;
+        }
 
-            self.selection.write_sql(f, bind_count)?;
+        Ok(())
+    }
 
-            write!(f, " FROM ")?;
-            self.query.from.write_sql(f, bind_count)?;
+    fn to_sql_string<W: Write>(&self, f: &mut W, bind_count: &mut BindCount) -> fmt::Result {
+        if let Some(explain) = self.query.explain {
+            explain.write_sql(f, bind_count)?;
+        }
 
-            for join in &self.query.joins {
-                write!(f, " ")?;
-                join.write_sql(f, bind_count)?;
-            }
+        self.query.ctes.write_sql(f, bind_count)?;
 
-            if let Some(filter) = &self.query.filter {
-                write!(f, " WHERE ")?;
-                filter.write_sql(f, bind_count)?;
+        if let Some(Distinct::On(cols)) = &self.query.distinct {
+            if bind_count.render_mode(Feature::DistinctOn)? == RenderMode::Emulated {
+                return self.write_emulated_distinct_on(cols, f, bind_count);
             }
+        }
 
-            if let Some(group) = &self.query.group {
-                write!(f, " GROUP BY ")?;
-                group.write_sql(f, bind_count)?;
-            }
+        write!(f, "SELECT ")?;
 
-            if let Some(having) = &self.query.having {
-                write!(f, " HAVING ")?;
-                having.write_sql(f, bind_count)?;
-            }
+        if let Some(distinct) = &self.query.distinct {
+            distinct.write_sql(f, bind_count)?;
+        }
 
-            if let Some(order) = &self.query.order {
-                write!(f, " ORDER BY ")?;
-                order.write_sql(f, bind_count)?;
-            }
+        self.selection.write_sql(f, bind_count)?;
+
+        self.write_from_to_having(f, bind_count)?;
+
+        if let Some(order) = &self.query.order {
+            write!(f, " ORDER BY ")?;
+            order.write_sql(f, bind_count)?;
+        }
+
+        self.write_limit_and_offset(f, bind_count)?;
+
+        self.query.row_locking.write_sql(f, bind_count)?;
+
+        Ok(())
+    }
+
+    fn write_from_to_having<W: Write>(&self, f: &mut W, bind_count: &mut BindCount) -> fmt::Result {
+        write!(f, " FROM ")?;
+        self.query.from.write_sql(f, bind_count)?;
+
+        for join in &self.query.joins {
+            write!(f, " ")?;
+            join.write_sql(f, bind_count)?;
+        }
+
+        if let Some(filter) = &self.query.filter {
+            write!(f, " WHERE ")?;
+            filter.write_sql(f, bind_count)?;
+        }
+
+        if let Some(group) = &self.query.group {
+            write!(f, " GROUP BY ")?;
+            group.write_sql(f, bind_count)?;
+        }
+
+        if let Some(having) = &self.query.having {
+            write!(f, " HAVING ")?;
+            having.write_sql(f, bind_count)?;
+        }
+
+        Ok(())
+    }
 
-            if let Some(limit) = &self.query.limit {
-                write!(f, " LIMIT ")?;
-                limit.0.write_sql(f, bind_count)?;
+    fn write_limit_and_offset<W: Write>(
+        &self,
+        f: &mut W,
+        bind_count: &mut BindCount,
+    ) -> fmt::Result {
+        if let Some(limit) = &self.query.limit {
+            write!(f, " LIMIT ")?;
+            limit.0.write_sql(f, bind_count)?;
+        }
+
+        if let Some(offset) = &self.query.offset {
+            if self.query.limit.is_none()
+                && bind_count.render_mode(Feature::OffsetWithoutLimit)? == RenderMode::Emulated
+            {
+                write!(f, " LIMIT {}", bind_count.unbounded_limit())?;
             }
+            write!(f, " OFFSET ")?;
+            offset.0.write_sql(f, bind_count)?;
+        }
+
+        Ok(())
+    }
+
+    // Keeps the first row of each partition by numbering them with `ROW_NUMBER()` in a sub query.
+    // Sub queries don't have to keep their order, so the `ORDER BY` columns are selected as well
+    // and the outer query orders by them.
+    fn write_emulated_distinct_on<W: Write>(
+        &self,
+        cols: &[Column],
+        f: &mut W,
+        bind_count: &mut BindCount,
+    ) -> fmt::Result {
+        const ROW_NUMBER: &str = "__row_number";
+        const SUB_QUERY: &str = "__distinct_on";
+
+        let order_alias = |idx: usize| format!("__order_{}", idx);
+        let orderings = self
+            .query
+            .order
+            .as_ref()
+            .map(Order::orderings)
+            .unwrap_or(&[]);
+        if orderings.iter().any(|ordering| ordering.expr().is_none()) {
+            return bind_count.fail(Error::UnsupportedEmulation {
+                dialect: bind_count.dialect_name(),
+                feature: Feature::DistinctOn,
+            });
+        }
+
+        write!(f, "SELECT * FROM (SELECT ")?;
+        self.selection.write_sql(f, bind_count)?;
+
+        write!(f, ", ROW_NUMBER() OVER (PARTITION BY ")?;
+        cols.iter().write_sql(f, bind_count)?;
+        if let Some(order) = &self.query.order {
+            write!(f, " ORDER BY ")?;
+            order.write_sql(f, bind_count)?;
+        }
+        write!(f, ") AS ")?;
+        bind_count.write_identifier(f, ROW_NUMBER)?;
+
+        for (idx, expr) in orderings.iter().filter_map(Ordering::expr).enumerate() {
+            write!(f, ", ")?;
+            expr.write_sql(f, bind_count)?;
+            write!(f, " AS ")?;
+            bind_count.write_identifier(f, &order_alias(idx))?;
+        }
+
+        self.write_from_to_having(f, bind_count)?;
 
-            if let Some(offset) = &self.query.offset {
-                write!(f, " OFFSET ")?;
-                offset.0.write_sql(f, bind_count)?;
+        write!(f, ") AS ")?;
+        bind_count.write_identifier(f, SUB_QUERY)?;
+        write!(f, " WHERE ")?;
+        bind_count.write_identifier(f, ROW_NUMBER)?;
+        write!(f, " = 1")?;
+
+        for (idx, ordering) in orderings.iter().enumerate() {
+            if idx == 0 {
+                write!(f, " ORDER BY ")?;
+            } else {
+                write!(f, ", ")?;
             }
+            ordering.write_sql_with(f, bind_count, |f, bind_count| {
+                bind_count.write_identifier(f, &order_alias(idx))
+            })?;
+        }
 
-            self.query.row_locking.write_sql(f, bind_count)?;
+        self.write_limit_and_offset(f, bind_count)?;
 
-            Ok(())
-        })();
+        self.query.row_locking.write_sql(f, bind_count)?;
 
-        result.expect("WriteSql should never fail");
+        Ok(())
     }
 
+    // In the order the binds appear in the SQL
     fn collect_binds(&self, binds: &mut BindsInternal) {
-        self.query.collect_binds(binds);
+        self.query.ctes.collect_binds(binds);
+        self.selection.collect_binds(binds);
+
+        match (&self.query.distinct, &self.query.order) {
+            // The emulated `DISTINCT ON` writes the `ORDER BY` in the window and selects its
+            // expressions, both before `FROM`
+            (Some(Distinct::On(_)), Some(order))
+                if binds.render_mode(Feature::DistinctOn) == RenderMode::Emulated =>
+            {
+                order.collect_binds(binds);
+                for expr in order.orderings().iter().filter_map(Ordering::expr) {
+                    expr.collect_binds(binds);
+                }
+                self.query.collect_from_to_having_binds(binds);
+                self.query.collect_limit_to_row_locking_binds(binds);
+            }
+            _ => self.query.collect_binds_after_select(binds),
+        }
     }
 
     pub fn cast_to<K>(self) -> QueryWithSelect<K> {
@@ -343,35 +594,77 @@ impl<T> QueryWithSelect<T> {
         }
     }
 
-    pub fn union<K>(self, other: QueryWithSelect<K>) -> Union<T> {
-        Union::Pair(UnionKind::Default, self, other.cast_to())
+    pub fn union<K>(self, other: impl Into<SetOperation<K>>) -> SetOperation<T> {
+        SetOperation::from(self).union(other)
+    }
+
+    pub fn union_all<K>(self, other: impl Into<SetOperation<K>>) -> SetOperation<T> {
+        SetOperation::from(self).union_all(other)
+    }
+
+    pub fn union_distinct<K>(self, other: impl Into<SetOperation<K>>) -> SetOperation<T> {
+        SetOperation::from(self).union_distinct(other)
+    }
+
+    pub fn intersect<K>(self, other: impl Into<SetOperation<K>>) -> SetOperation<T> {
+        SetOperation::from(self).intersect(other)
+    }
+
+    pub fn intersect_all<K>(self, other: impl Into<SetOperation<K>>) -> SetOperation<T> {
+        SetOperation::from(self).intersect_all(other)
+    }
+
+    pub fn intersect_distinct<K>(self, other: impl Into<SetOperation<K>>) -> SetOperation<T> {
+        SetOperation::from(self).intersect_distinct(other)
+    }
+
+    pub fn except<K>(self, other: impl Into<SetOperation<K>>) -> SetOperation<T> {
+        SetOperation::from(self).except(other)
+    }
+
+    pub fn except_all<K>(self, other: impl Into<SetOperation<K>>) -> SetOperation<T> {
+        SetOperation::from(self).except_all(other)
     }
 
-    pub fn union_all<K>(self, other: QueryWithSelect<K>) -> Union<T> {
-        Union::Pair(UnionKind::All, self, other.cast_to())
+    pub fn except_distinct<K>(self, other: impl Into<SetOperation<K>>) -> SetOperation<T> {
+        SetOperation::from(self).except_distinct(other)
     }
 
-    pub fn union_distinct<K>(self, other: QueryWithSelect<K>) -> Union<T> {
-        Union::Pair(UnionKind::Distinct, self, other.cast_to())
+    // Postgres only allows these clauses on an operand of a set operation if it is
+    // wrapped in parentheses
+    fn needs_parens_in_set_operation(&self) -> bool {
+        !self.query.ctes.is_empty()
+            || self.query.order.is_some()
+            || self.query.limit.is_some()
+            || self.query.offset.is_some()
+            || self.query.row_locking.is_locking()
     }
 }
 
 impl<T> WriteSql for &QueryWithSelect<T> {
     fn write_sql<W: Write>(self, f: &mut W, bind_count: &mut BindCount) -> fmt::Result {
-        self.to_sql_string(f, bind_count);
-        Ok(())
+        self.to_sql_string(f, bind_count)
     }
 }
 
 impl<T> CollectBinds for Box<QueryWithSelect<T>> {
     fn collect_binds(&self, binds: &mut BindsInternal) {
-        self.query.collect_binds(binds)
+        (**self).collect_binds(binds)
     }
 }
 
-impl<T> CollectBinds for Query<T> {
-    fn collect_binds(&self, binds: &mut BindsInternal) {
-        self.ctes.collect_binds(binds);
+impl<T> Query<T> {
+    fn collect_binds_after_select(&self, binds: &mut BindsInternal) {
+        self.collect_from_to_having_binds(binds);
+
+        if let Some(order) = &self.order {
+            order.collect_binds(binds);
+        }
+
+        self.collect_limit_to_row_locking_binds(binds);
+    }
+
+    fn collect_from_to_having_binds(&self, binds: &mut BindsInternal) {
         self.from.collect_binds(binds);
         self.joins.collect_binds(binds);
 
@@ -386,11 +679,9 @@ impl<T> CollectBinds for Query<T> {
         if let Some(having) = &self.having {
             having.collect_binds(binds);
         }
+    }
 
-        if let Some(order) = &self.order {
-            order.collect_binds(binds);
-        }
-
+    fn collect_limit_to_row_locking_binds(&self, binds: &mut BindsInternal) {
         if let Some(limit) = &self.limit {
             limit.0.collect_binds(binds);
         }
@@ -403,6 +694,17 @@ impl<T> CollectBinds for Query<T> {
     }
 }
 
+fn check_bind_count(binds: &Binds, dialect: &dyn Dialect) -> Result<(), Error> {
+    if binds.len() > dialect.max_binds() {
+        Err(Error::TooManyBinds {
+            count: binds.len(),
+            max: dialect.max_binds(),
+        })
+    } else {
+        Ok(())
+    }
+}
+
 impl CollectBinds for Table {
     fn collect_binds(&self, _: &mut BindsInternal) {}
 }
@@ -473,94 +775,20 @@ impl_into_columns!(
     T22, T23, T24, T25, T26, T27, T28, T29, T30, T31, T32,
 );
 
-#[derive(Debug)]
-pub enum UnionKind {
-    Default,
-    All,
-    Distinct,
-}
-
-#[derive(Debug)]
-pub enum Union<T> {
-    Pair(UnionKind, QueryWithSelect<T>, QueryWithSelect<T>),
-    And(UnionKind, Box<Union<T>>, QueryWithSelect<T>),
-}
-
-impl WriteSql for &UnionKind {
-    fn write_sql<W: Write>(self, f: &mut W, _: &mut BindCount) -> fmt::Result {
-        match self {
-            UnionKind::Default => write!(f, " UNION "),
-            UnionKind::All => write!(f, " UNION ALL "),
-            UnionKind::Distinct => write!(f, " UNION DISTINCT "),
-        }
-    }
-}
-
-impl<T> Union<T> {
-    pub fn to_sql(self) -> (String, Binds) {
-        let mut bind_count = BindCount::new();
-        let mut sql = String::new();
-
-        self.to_sql_recurs(&mut sql, &mut bind_count);
-
-        let mut binds = BindsInternal::with_capacity(bind_count.count());
-        self.collect_binds_recurs(&mut binds);
-
-        (sql, Binds::from(binds))
-    }
-
-    fn to_sql_recurs(&self, sql: &mut String, bind_count: &mut BindCount) {
-        match self {
-            Union::Pair(kind, lhs, rhs) => {
-                lhs.to_sql_string(sql, bind_count);
-                kind.write_sql(sql, bind_count).unwrap();
-                rhs.to_sql_string(sql, bind_count);
-            }
-            Union::And(kind, head, tail) => {
-                head.to_sql_recurs(sql, bind_count);
-                kind.write_sql(sql, bind_count).unwrap();
-                tail.to_sql_string(sql, bind_count);
-            }
-        }
-    }
-
-    fn collect_binds_recurs(&self, binds: &mut BindsInternal) {
-        match self {
-            Union::Pair(_, lhs, rhs) => {
-                lhs.collect_binds(binds);
-                rhs.collect_binds(binds);
-            }
-            Union::And(_, head, tail) => {
-                head.collect_binds_recurs(binds);
-                tail.collect_binds(binds);
-            }
-        }
-    }
-
-    pub fn union<K>(self, other: QueryWithSelect<K>) -> Union<T> {
-        Union::And(UnionKind::Default, Box::new(self), other.cast_to())
-    }
-
-    pub fn union_all<K>(self, other: QueryWithSelect<K>) -> Union<T> {
-        Union::And(UnionKind::All, Box::new(self), other.cast_to())
-    }
-
-    pub fn union_distinct<K>(self, other: QueryWithSelect<K>) -> Union<T> {
-        Union::And(UnionKind::Distinct, Box::new(self), other.cast_to())
-    }
-}
-
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum Explain {
     Default,
     Analyze,
 }
 
 impl WriteSql for Explain {
-    fn write_sql<W: Write>(self, f: &mut W, _: &mut BindCount) -> fmt::Result {
+    fn write_sql<W: Write>(self, f: &mut W, bind_count: &mut BindCount) -> fmt::Result {
         match self {
             Explain::Default => write!(f, "EXPLAIN "),
-            Explain::Analyze => write!(f, "EXPLAIN ANALYZE "),
+            Explain::Analyze => {
+                bind_count.require(Feature::ExplainAnalyze)?;
+                write!(f, "EXPLAIN ANALYZE ")
+            }
         }
     }
 }
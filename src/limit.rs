@@ -2,16 +2,16 @@ use crate::binds::{Bind, BindCount, BindsInternal, CollectBinds};
 use crate::WriteSql;
 use std::fmt::{self, Write};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Limit(pub(crate) LimitI);
 
-impl Into<Limit> for LimitI {
-    fn into(self) -> Limit {
-        Limit(self)
+impl From<LimitI> for Limit {
+    fn from(limit: LimitI) -> Self {
+        Limit(limit)
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub(crate) enum LimitI {
     Count(i32),
     Raw(String),
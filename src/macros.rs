@@ -3,7 +3,7 @@ macro_rules! table {
     (
         $table:ident {
             $(
-                $col:ident -> $ty:ident,
+                $col:ident -> $ty:ty,
             )*
         }
     ) => {
@@ -12,9 +12,10 @@ macro_rules! table {
             dead_code,
             missing_docs,
             non_camel_case_types,
+            unused_imports,
         )]
         mod $table {
-            use $crate::sql_types::{Integer, Text};
+            use $crate::sql_types::*;
 
             pub mod dsl {
                 pub use super::table as $table;
@@ -24,7 +25,7 @@ macro_rules! table {
                 )*
             }
 
-            #[derive(Copy, Clone)]
+            #[derive(Debug, Copy, Clone)]
             pub struct table;
 
             impl From<table> for $crate::Table {
@@ -45,25 +46,25 @@ macro_rules! table {
             //     }
             // }
 
-            #[derive(Copy, Clone)]
+            #[derive(Debug, Copy, Clone)]
             pub struct star;
 
-            impl Into<$crate::Select> for star {
-                fn into(self) -> $crate::Select {
+            impl From<star> for $crate::Select {
+                fn from(s: star) -> $crate::Select {
                     $crate::Select::Simple(
                         $crate::Selection::TableStar($crate::Table::from(table))
                     )
                 }
             }
 
-            impl Into<$crate::Selection> for star {
-                fn into(self) -> $crate::Selection {
+            impl From<star> for $crate::Selection {
+                fn from(s: star) -> $crate::Selection {
                     $crate::Selection::TableStar($crate::Table::from(table))
                 }
             }
 
             $(
-                #[derive(Copy, Clone)]
+                #[derive(Debug, Copy, Clone)]
                 pub struct $col;
 
                 impl From<$col> for $crate::Select {
@@ -0,0 +1,248 @@
+use crate::limit::LimitI;
+use crate::offset::OffsetI;
+use crate::{Distinct, Error, Explain, Filter, Group, Join, Limit, Offset, Order, Query};
+
+/// How to resolve a clause that is set on both sides of `QueryDsl::merge_with`.
+///
+/// A clause that is only set on one side is always kept.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum MergePolicy {
+    KeepLeft,
+    KeepRight,
+    /// Combine both clauses. What that means depends on the clause:
+    ///
+    /// - `GROUP BY`: the left columns followed by the right columns
+    /// - `HAVING`: both conditions joined with `AND`
+    /// - `ORDER BY`: the left orderings followed by the right orderings
+    /// - `LIMIT`: the smaller of the two limits
+    /// - `OFFSET`: the larger of the two offsets
+    /// - `DISTINCT`: the columns of both `DISTINCT ON`s. Mixing `DISTINCT` and `DISTINCT ON` is
+    ///   an error.
+    /// - Row locking: every lock from both sides
+    /// - `EXPLAIN`: `EXPLAIN ANALYZE` if either side uses it
+    /// - Joins: one join of the table with both conditions joined with `AND`. Joining the same
+    ///   table with different kinds of join is an error.
+    ///
+    /// Raw limits and offsets cannot be compared so combining them is an error.
+    Combine,
+    Error,
+}
+
+/// Conflict resolution for each clause used by `QueryDsl::merge_with`.
+///
+/// `WHERE` clauses and CTEs are always combined. Joins that are identical on both sides are only
+/// kept once, and `joins` decides what happens when both sides join the same table differently.
+///
+/// The default options never conflict, so `QueryDsl::merge` can't fail.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct MergeOptions {
+    pub joins: MergePolicy,
+    pub group: MergePolicy,
+    pub having: MergePolicy,
+    pub order: MergePolicy,
+    pub limit: MergePolicy,
+    pub offset: MergePolicy,
+    pub distinct: MergePolicy,
+    pub row_locking: MergePolicy,
+    pub explain: MergePolicy,
+}
+
+impl Default for MergeOptions {
+    fn default() -> Self {
+        Self {
+            joins: MergePolicy::KeepLeft,
+            group: MergePolicy::Combine,
+            having: MergePolicy::Combine,
+            order: MergePolicy::Combine,
+            limit: MergePolicy::KeepLeft,
+            offset: MergePolicy::KeepLeft,
+            distinct: MergePolicy::KeepLeft,
+            row_locking: MergePolicy::Combine,
+            explain: MergePolicy::KeepLeft,
+        }
+    }
+}
+
+fn resolve<T>(
+    clause: &'static str,
+    policy: MergePolicy,
+    lhs: Option<T>,
+    rhs: Option<T>,
+    combine: impl FnOnce(T, T) -> Option<T>,
+) -> Result<Option<T>, Error> {
+    match (lhs, rhs) {
+        (Some(lhs), Some(rhs)) => match policy {
+            MergePolicy::KeepLeft => Ok(Some(lhs)),
+            MergePolicy::KeepRight => Ok(Some(rhs)),
+            MergePolicy::Combine => combine(lhs, rhs)
+                .map(Some)
+                .ok_or(Error::MergeConflict { clause }),
+            MergePolicy::Error => Err(Error::MergeConflict { clause }),
+        },
+        (Some(lhs), None) => Ok(Some(lhs)),
+        (None, Some(rhs)) => Ok(Some(rhs)),
+        (None, None) => Ok(None),
+    }
+}
+
+impl<T> Query<T> {
+    pub(crate) fn merge_query<K>(
+        mut self,
+        other: Query<K>,
+        options: MergeOptions,
+    ) -> Result<Query<T>, Error> {
+        let rhs = other.cast_to::<T>();
+
+        self.filter = match (self.filter, rhs.filter) {
+            (Some(a), Some(b)) => Some(Filter::And(Box::new(a), Box::new(b))),
+            (Some(a), None) => Some(a),
+            (None, Some(b)) => Some(b),
+            (None, None) => None,
+        };
+
+        self.merge_joins(rhs.joins, options.joins)?;
+        self.ctes.extend(rhs.ctes);
+
+        self.group = resolve("group by", options.group, self.group, rhs.group, |a, b| {
+            Some(Group::And {
+                lhs: Box::new(a),
+                rhs: Box::new(b),
+            })
+        })?;
+
+        self.having = resolve("having", options.having, self.having, rhs.having, |a, b| {
+            Some(a.and(b))
+        })?;
+
+        self.order = resolve("order by", options.order, self.order, rhs.order, |a, b| {
+            let mut orderings = a.into_orderings();
+            orderings.extend(b.into_orderings());
+            Some(Order::List(orderings))
+        })?;
+
+        self.limit = resolve(
+            "limit",
+            options.limit,
+            self.limit,
+            rhs.limit,
+            |a, b| match (a.0, b.0) {
+                (LimitI::Count(a), LimitI::Count(b)) => Some(Limit::from(a.min(b))),
+                _ => None,
+            },
+        )?;
+
+        self.offset = resolve(
+            "offset",
+            options.offset,
+            self.offset,
+            rhs.offset,
+            |a, b| match (a.0, b.0) {
+                (OffsetI::Count(a), OffsetI::Count(b)) => Some(Offset::from(a.max(b))),
+                _ => None,
+            },
+        )?;
+
+        self.distinct = resolve(
+            "distinct",
+            options.distinct,
+            self.distinct,
+            rhs.distinct,
+            |a, b| match (a, b) {
+                (Distinct::EachRow, Distinct::EachRow) => Some(Distinct::EachRow),
+                (Distinct::On(mut a), Distinct::On(b)) => {
+                    for col in b {
+                        if !a.contains(&col) {
+                            a.push(col);
+                        }
+                    }
+                    Some(Distinct::On(a))
+                }
+                _ => None,
+            },
+        )?;
+
+        let lhs_locking = Some(self.row_locking).filter(|locking| locking.is_locking());
+        let rhs_locking = Some(rhs.row_locking).filter(|locking| locking.is_locking());
+        if let Some(row_locking) = resolve(
+            "row locking",
+            options.row_locking,
+            lhs_locking,
+            rhs_locking,
+            |a, b| Some(a.or(b)),
+        )? {
+            self.row_locking = row_locking;
+        }
+
+        self.explain = resolve(
+            "explain",
+            options.explain,
+            self.explain,
+            rhs.explain,
+            |a, b| match (a, b) {
+                (Explain::Default, Explain::Default) => Some(Explain::Default),
+                _ => Some(Explain::Analyze),
+            },
+        )?;
+
+        Ok(self)
+    }
+
+    fn merge_joins(&mut self, joins: Vec<Join<T>>, policy: MergePolicy) -> Result<(), Error> {
+        for join in joins {
+            if self.joins.contains(&join) {
+                continue;
+            }
+
+            if let Join::Known { from, .. } = &join {
+                let conflict = self.joins.iter().position(|existing| match existing {
+                    Join::Known { from: other, .. } => other.name() == from.name(),
+                    _ => false,
+                });
+
+                if let Some(idx) = conflict {
+                    match policy {
+                        MergePolicy::KeepLeft => continue,
+                        MergePolicy::KeepRight => {
+                            self.joins[idx] = join;
+                            continue;
+                        }
+                        MergePolicy::Combine => {
+                            combine_joins(&mut self.joins[idx], join)?;
+                            continue;
+                        }
+                        MergePolicy::Error => {
+                            return Err(Error::JoinConflict {
+                                table: from.name().to_string(),
+                            });
+                        }
+                    }
+                }
+            }
+
+            self.joins.push(join);
+        }
+
+        Ok(())
+    }
+}
+
+// Only called for joins of the same table, which are always `Join::Known`
+fn combine_joins<T>(existing: &mut Join<T>, join: Join<T>) -> Result<(), Error> {
+    if let (
+        Join::Known { kind, filter, .. },
+        Join::Known {
+            kind: other_kind,
+            from,
+            filter: other_filter,
+        },
+    ) = (existing, join)
+    {
+        if *kind != other_kind {
+            return Err(Error::JoinConflict {
+                table: from.name().to_string(),
+            });
+        }
+        *filter = filter.clone().and(other_filter);
+    }
+    Ok(())
+}
@@ -2,12 +2,12 @@ use crate::binds::{Bind, BindCount, BindsInternal, CollectBinds};
 use crate::WriteSql;
 use std::fmt::{self, Write};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Offset(pub(crate) OffsetI);
 
-impl Into<Offset> for OffsetI {
-    fn into(self) -> Offset {
-        Offset(self)
+impl From<OffsetI> for Offset {
+    fn from(offset: OffsetI) -> Self {
+        Offset(offset)
     }
 }
 
@@ -26,7 +26,7 @@ where
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub(crate) enum OffsetI {
     Count(i32),
     Raw(String),
@@ -1,10 +1,11 @@
 use crate::binds::BindCount;
 use crate::binds::{BindsInternal, CollectBinds};
-use crate::{Column, WriteSql};
-use itertools::{Itertools, Position};
+use crate::dialect::{Feature, RenderMode};
+use crate::validate::{CollectTableRefs, TableRef};
+use crate::{Column, Expr, IntoExpr, TypedExpr, WriteSql};
 use std::fmt::{self, Write};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Order {
     Simple(Ordering),
     List(Vec<Ordering>),
@@ -22,6 +23,20 @@ impl Order {
         }
     }
 
+    pub(crate) fn orderings(&self) -> &[Ordering] {
+        match self {
+            Order::Simple(inner) => std::slice::from_ref(inner),
+            Order::List(inners) => inners,
+        }
+    }
+
+    pub(crate) fn into_orderings(self) -> Vec<Ordering> {
+        match self {
+            Order::Simple(inner) => vec![inner],
+            Order::List(inners) => inners,
+        }
+    }
+
     pub(crate) fn extend(&mut self, mut ordering: Vec<Ordering>) {
         match self {
             Order::Simple(inner) => {
@@ -35,15 +50,42 @@ impl Order {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Ordering {
-    Default(Column, Option<NullsPosition>),
-    Asc(Column, Option<NullsPosition>),
-    Desc(Column, Option<NullsPosition>),
+    Default(Expr, Option<NullsPosition>),
+    Asc(Expr, Option<NullsPosition>),
+    Desc(Expr, Option<NullsPosition>),
     Raw(String),
 }
 
-#[derive(Debug, Clone, Copy)]
+impl Ordering {
+    pub(crate) fn expr(&self) -> Option<&Expr> {
+        match self {
+            Ordering::Default(expr, _) | Ordering::Asc(expr, _) | Ordering::Desc(expr, _) => {
+                Some(expr)
+            }
+            Ordering::Raw(_) => None,
+        }
+    }
+
+    pub(crate) fn nulls_position(&self) -> Option<NullsPosition> {
+        match self {
+            Ordering::Default(_, nulls_position)
+            | Ordering::Asc(_, nulls_position)
+            | Ordering::Desc(_, nulls_position) => *nulls_position,
+            Ordering::Raw(_) => None,
+        }
+    }
+
+    pub(crate) fn column(&self) -> Option<&Column> {
+        match self.expr() {
+            Some(Expr::Column(col)) => Some(col),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum NullsPosition {
     First,
     Last,
@@ -67,40 +109,63 @@ impl WriteSql for &Order {
     }
 }
 
-impl WriteSql for &Ordering {
-    fn write_sql<W: Write>(self, f: &mut W, bind_count: &mut BindCount) -> fmt::Result {
-        let nulls_position = match self {
-            Ordering::Default(col, nulls_position) => {
-                col.write_sql(f, bind_count)?;
-                nulls_position
-            }
-            Ordering::Asc(col, nulls_position) => {
-                col.write_sql(f, bind_count)?;
-                write!(f, " ASC")?;
-                nulls_position
-            }
-            Ordering::Desc(col, nulls_position) => {
-                col.write_sql(f, bind_count)?;
-                write!(f, " DESC")?;
-                nulls_position
-            }
-            Ordering::Raw(sql) => {
-                write!(f, "{}", sql)?;
-                &None
-            }
+impl Ordering {
+    // Writes the direction and nulls position around `write_expr`, so orderings can be
+    // rendered against something other than their own column.
+    pub(crate) fn write_sql_with<W: Write>(
+        &self,
+        f: &mut W,
+        bind_count: &mut BindCount,
+        write_expr: impl Fn(&mut W, &mut BindCount) -> fmt::Result,
+    ) -> fmt::Result {
+        let (direction, nulls_position) = match self {
+            Ordering::Default(_, nulls_position) => ("", nulls_position),
+            Ordering::Asc(_, nulls_position) => (" ASC", nulls_position),
+            Ordering::Desc(_, nulls_position) => (" DESC", nulls_position),
+            Ordering::Raw(sql) => return write!(f, "{}", sql),
+        };
+
+        let nulls_position = match nulls_position {
+            Some(nulls_position) => Some((
+                nulls_position,
+                bind_count.render_mode(Feature::NullsPosition)?,
+            )),
+            None => None,
         };
 
         match nulls_position {
-            Some(NullsPosition::First) => {
-                write!(f, " NULLS FIRST")?;
+            Some((nulls_position, RenderMode::Emulated)) => {
+                // `IS NULL` is 1 for nulls, so sorting it descending puts the nulls first
+                write_expr(f, bind_count)?;
+                match nulls_position {
+                    NullsPosition::First => write!(f, " IS NULL DESC, ")?,
+                    NullsPosition::Last => write!(f, " IS NULL, ")?,
+                }
+                write_expr(f, bind_count)?;
+                write!(f, "{}", direction)
             }
-            Some(NullsPosition::Last) => {
-                write!(f, " NULLS LAST")?;
+            Some((nulls_position, RenderMode::Native)) => {
+                write_expr(f, bind_count)?;
+                write!(f, "{}", direction)?;
+                match nulls_position {
+                    NullsPosition::First => write!(f, " NULLS FIRST"),
+                    NullsPosition::Last => write!(f, " NULLS LAST"),
+                }
+            }
+            None => {
+                write_expr(f, bind_count)?;
+                write!(f, "{}", direction)
             }
-            None => {}
         }
+    }
+}
 
-        Ok(())
+impl WriteSql for &Ordering {
+    fn write_sql<W: Write>(self, f: &mut W, bind_count: &mut BindCount) -> fmt::Result {
+        self.write_sql_with(f, bind_count, |f, bind_count| match self.expr() {
+            Some(expr) => expr.write_sql(f, bind_count),
+            None => Ok(()),
+        })
     }
 }
 
@@ -119,12 +184,24 @@ where
     }
 }
 
+impl<T> From<TypedExpr<T>> for Order {
+    fn from(expr: TypedExpr<T>) -> Self {
+        Order::Simple(Ordering::from(expr))
+    }
+}
+
 impl<T> From<T> for Ordering
 where
     T: Into<Column>,
 {
     fn from(col: T) -> Self {
-        Ordering::Default(col.into(), None)
+        Ordering::Default(Expr::Column(col.into()), None)
+    }
+}
+
+impl<T> From<TypedExpr<T>> for Ordering {
+    fn from(expr: TypedExpr<T>) -> Self {
+        Ordering::Default(expr.into_expr(), None)
     }
 }
 
@@ -139,11 +216,21 @@ where
     T: Into<Column>,
 {
     fn asc(self) -> Ordering {
-        Ordering::Asc(self.into(), None)
+        Ordering::Asc(Expr::Column(self.into()), None)
     }
 
     fn desc(self) -> Ordering {
-        Ordering::Desc(self.into(), None)
+        Ordering::Desc(Expr::Column(self.into()), None)
+    }
+}
+
+impl<T> OrderDsl for TypedExpr<T> {
+    fn asc(self) -> Ordering {
+        Ordering::Asc(self.into_expr(), None)
+    }
+
+    fn desc(self) -> Ordering {
+        Ordering::Desc(self.into_expr(), None)
     }
 }
 
@@ -168,7 +255,33 @@ impl OrderDsl for Ordering {
 }
 
 impl CollectBinds for Order {
-    fn collect_binds(&self, _: &mut BindsInternal) {}
+    fn collect_binds(&self, binds: &mut BindsInternal) {
+        for ordering in self.orderings() {
+            ordering.collect_binds(binds);
+        }
+    }
+}
+
+impl CollectBinds for Ordering {
+    fn collect_binds(&self, binds: &mut BindsInternal) {
+        if let Some(expr) = self.expr() {
+            expr.collect_binds(binds);
+            // The emulated nulls position writes the expression twice
+            if self.nulls_position().is_some()
+                && binds.render_mode(Feature::NullsPosition) == RenderMode::Emulated
+            {
+                expr.collect_binds(binds);
+            }
+        }
+    }
+}
+
+impl CollectTableRefs for Order {
+    fn collect_table_refs<'a>(&'a self, refs: &mut Vec<TableRef<'a>>) {
+        for expr in self.orderings().iter().filter_map(Ordering::expr) {
+            expr.collect_table_refs(refs);
+        }
+    }
 }
 
 pub trait NullsPositionDsl {
@@ -182,11 +295,11 @@ where
     T: Into<Column>,
 {
     fn nulls_first(self) -> Ordering {
-        Ordering::Default(self.into(), Some(NullsPosition::First))
+        Ordering::Default(Expr::Column(self.into()), Some(NullsPosition::First))
     }
 
     fn nulls_last(self) -> Ordering {
-        Ordering::Default(self.into(), Some(NullsPosition::Last))
+        Ordering::Default(Expr::Column(self.into()), Some(NullsPosition::Last))
     }
 }
 
@@ -0,0 +1,402 @@
+use crate::binds::Bind;
+use crate::order::{NullsPosition, Ordering};
+use crate::{BinOp, Error, Expr, Filter, Query, QueryDsl, QueryWithSelect, UnOp};
+use std::convert::TryInto;
+
+/// The position of a row in a keyset paginated query, made from its `ORDER BY` values.
+///
+/// ```
+/// use dilemma::Cursor;
+///
+/// let token = Cursor::new().value(3).nullable_value(Some("Bob")).encode().unwrap();
+/// assert_eq!(
+///     Cursor::decode(&token).unwrap(),
+///     Cursor::new().value(3).value("Bob")
+/// );
+/// ```
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Cursor {
+    values: Vec<Option<Bind>>,
+}
+
+impl Cursor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the value of the next `ORDER BY` expression.
+    pub fn value(mut self, value: impl Into<Bind>) -> Self {
+        self.values.push(Some(value.into()));
+        self
+    }
+
+    /// Adds the value of the next `ORDER BY` expression, which can be NULL.
+    pub fn nullable_value<T: Into<Bind>>(mut self, value: Option<T>) -> Self {
+        self.values.push(value.map(Into::into));
+        self
+    }
+
+    pub fn values(&self) -> &[Option<Bind>] {
+        &self.values
+    }
+
+    /// Encodes the cursor as an opaque URL safe token. Fails for custom types and arrays, whose
+    /// type names can't be decoded.
+    pub fn encode(&self) -> Result<String, Error> {
+        let mut bytes = vec![CURSOR_VERSION];
+        for value in &self.values {
+            encode_value(value.as_ref(), &mut bytes)?;
+        }
+        Ok(base64_encode(&bytes))
+    }
+
+    pub fn decode(token: &str) -> Result<Self, Error> {
+        let bytes = base64_decode(token).ok_or(Error::InvalidCursor)?;
+        let mut reader = Reader(&bytes);
+        if reader.take(1)? != [CURSOR_VERSION] {
+            return Err(Error::InvalidCursor);
+        }
+
+        let mut values = Vec::new();
+        while !reader.0.is_empty() {
+            values.push(decode_value(&mut reader)?);
+        }
+        Ok(Self { values })
+    }
+}
+
+impl<T> Query<T> {
+    /// Keyset pagination: keeps the first `page_size` rows that come after `cursor` in the
+    /// query's `ORDER BY`, or the first page if there is no cursor.
+    ///
+    /// The cursor must hold the values of the last row of the previous page, one for each
+    /// `ORDER BY` expression. Columns that can be NULL need an explicit `NULLS FIRST` or
+    /// `NULLS LAST`, so they sort the same way in every dialect.
+    pub fn paginate_after(self, cursor: Option<&Cursor>, page_size: i32) -> Result<Self, Error> {
+        let orderings = match &self.order {
+            Some(order) => order.orderings(),
+            None => return Err(Error::UnsupportedPaginationOrder),
+        };
+        let keys = orderings
+            .iter()
+            .map(|ordering| Key::new(ordering).ok_or(Error::UnsupportedPaginationOrder))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let query = match cursor {
+            Some(cursor) => {
+                if cursor.values.len() != keys.len() {
+                    return Err(Error::InvalidCursor);
+                }
+                let filter = keyset_filter(&keys, &cursor.values)?;
+                self.filter(filter)
+            }
+            None => self,
+        };
+
+        Ok(query.limit(page_size))
+    }
+}
+
+impl<T: Clone> QueryWithSelect<T> {
+    /// Offset pagination: the query for `page` (starting at 1) with `per_page` rows, and a
+    /// query for the `count(*)` of all the pages. Pages have at least one row, so a `per_page`
+    /// below 1 is treated as 1.
+    ///
+    /// The count keeps the `FROM`, joins, `WHERE` and `GROUP BY` but not the `ORDER BY`, `LIMIT`
+    /// and `OFFSET`, see `count_query`.
+    pub fn paginate(self, page: i32, per_page: i32) -> (QueryWithSelect<T>, QueryWithSelect<T>) {
+        let QueryWithSelect { query, selection } = self;
+
+        let count_query = QueryWithSelect {
+            query: query.clone().remove_limit().remove_offset(),
+            selection: selection.clone(),
+        }
+        .count_query();
+
+        let per_page = per_page.max(1);
+        let offset = (page.max(1) - 1).saturating_mul(per_page);
+        let page_query = query.limit(per_page).offset(offset).select(selection);
+
+        (page_query, count_query)
+    }
+}
+
+struct Key<'a> {
+    expr: &'a Expr,
+    descending: bool,
+    nulls: Option<NullsPosition>,
+}
+
+impl<'a> Key<'a> {
+    fn new(ordering: &'a Ordering) -> Option<Self> {
+        match ordering {
+            Ordering::Default(expr, nulls) | Ordering::Asc(expr, nulls) => Some(Key {
+                expr,
+                descending: false,
+                nulls: *nulls,
+            }),
+            Ordering::Desc(expr, nulls) => Some(Key {
+                expr,
+                descending: true,
+                nulls: *nulls,
+            }),
+            Ordering::Raw(_) => None,
+        }
+    }
+
+    // Rows whose key is equal to `value`
+    fn equal(&self, value: &Option<Bind>) -> Filter {
+        match value {
+            Some(value) => compare(self.expr, BinOp::Eq, value),
+            None => Filter::UnOp {
+                expr: self.expr.clone(),
+                op: UnOp::Null,
+            },
+        }
+    }
+
+    // Rows whose key comes after `value`
+    fn after(&self, value: &Option<Bind>) -> Result<Filter, Error> {
+        let op = if self.descending {
+            BinOp::Lt
+        } else {
+            BinOp::Gt
+        };
+        let is_null = |op| Filter::UnOp {
+            expr: self.expr.clone(),
+            op,
+        };
+
+        Ok(match (value, self.nulls) {
+            (Some(value), None) | (Some(value), Some(NullsPosition::First)) => {
+                compare(self.expr, op, value)
+            }
+            (Some(value), Some(NullsPosition::Last)) => {
+                compare(self.expr, op, value).or(is_null(UnOp::Null))
+            }
+            (None, Some(NullsPosition::First)) => is_null(UnOp::NotNull),
+            (None, Some(NullsPosition::Last)) => Filter::False,
+            (None, None) => return Err(Error::UnsupportedPaginationOrder),
+        })
+    }
+}
+
+fn compare(expr: &Expr, op: BinOp, value: &Bind) -> Filter {
+    Filter::BinOp {
+        lhs: expr.clone(),
+        op,
+        rhs: Expr::Bind(value.clone()),
+    }
+}
+
+// `(a, b) > ($1, $2)` when every key sorts the same way and NULLs don't matter, otherwise
+// `a > $1 OR a = $1 AND b > $2` with the comparison flipped for descending keys.
+fn keyset_filter(keys: &[Key<'_>], values: &[Option<Bind>]) -> Result<Filter, Error> {
+    let uniform = keys
+        .iter()
+        .all(|key| key.descending == keys[0].descending && key.nulls.is_none());
+    if uniform && keys.len() > 1 && values.iter().all(Option::is_some) {
+        let op = if keys[0].descending {
+            BinOp::Lt
+        } else {
+            BinOp::Gt
+        };
+        return Ok(Filter::BinOp {
+            lhs: Expr::Tuple(keys.iter().map(|key| key.expr.clone()).collect()),
+            op,
+            rhs: Expr::Tuple(values.iter().flatten().cloned().map(Expr::Bind).collect()),
+        });
+    }
+
+    let mut alternatives = Vec::with_capacity(keys.len());
+    for (idx, (key, value)) in keys.iter().zip(values).enumerate() {
+        let equal_before = keys[..idx]
+            .iter()
+            .zip(values)
+            .map(|(key, value)| key.equal(value));
+        alternatives.push(Filter::all(
+            equal_before.chain(std::iter::once(key.after(value)?)),
+        ));
+    }
+    Ok(Filter::any(alternatives).simplify())
+}
+
+const CURSOR_VERSION: u8 = 1;
+
+// Each value is a tag followed by its payload, with integers in big endian and strings and
+// bytes prefixed with their length
+fn encode_value(value: Option<&Bind>, out: &mut Vec<u8>) -> Result<(), Error> {
+    let bind = match value {
+        Some(bind) => bind,
+        None => {
+            out.push(0);
+            return Ok(());
+        }
+    };
+
+    let write_bytes = |tag: u8, bytes: &[u8], out: &mut Vec<u8>| {
+        out.push(tag);
+        out.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+        out.extend_from_slice(bytes);
+    };
+
+    match bind {
+        Bind::String(s) => write_bytes(1, s.as_bytes(), out),
+        Bind::I32(n) => {
+            out.push(2);
+            out.extend_from_slice(&n.to_be_bytes());
+        }
+        Bind::U64(n) => {
+            out.push(3);
+            out.extend_from_slice(&n.to_be_bytes());
+        }
+        Bind::I16(n) => {
+            out.push(4);
+            out.extend_from_slice(&n.to_be_bytes());
+        }
+        Bind::I64(n) => {
+            out.push(5);
+            out.extend_from_slice(&n.to_be_bytes());
+        }
+        Bind::Bool(b) => out.extend_from_slice(&[6, u8::from(*b)]),
+        Bind::F32(n) => {
+            out.push(7);
+            out.extend_from_slice(&n.to_bits().to_be_bytes());
+        }
+        Bind::F64(n) => {
+            out.push(8);
+            out.extend_from_slice(&n.to_bits().to_be_bytes());
+        }
+        Bind::Numeric(s) => write_bytes(9, s.as_bytes(), out),
+        Bind::Bytes(bytes) => write_bytes(10, bytes, out),
+        Bind::Date(days) => {
+            out.push(11);
+            out.extend_from_slice(&days.to_be_bytes());
+        }
+        Bind::Time(micros) => {
+            out.push(12);
+            out.extend_from_slice(&micros.to_be_bytes());
+        }
+        Bind::Timestamp(micros) => {
+            out.push(13);
+            out.extend_from_slice(&micros.to_be_bytes());
+        }
+        Bind::Timestamptz(micros) => {
+            out.push(14);
+            out.extend_from_slice(&micros.to_be_bytes());
+        }
+        Bind::Uuid(bytes) => {
+            out.push(15);
+            out.extend_from_slice(bytes);
+        }
+        Bind::Json(s) => write_bytes(16, s.as_bytes(), out),
+        Bind::Jsonb(s) => write_bytes(17, s.as_bytes(), out),
+        Bind::Interval {
+            months,
+            days,
+            micros,
+        } => {
+            out.push(18);
+            out.extend_from_slice(&months.to_be_bytes());
+            out.extend_from_slice(&days.to_be_bytes());
+            out.extend_from_slice(&micros.to_be_bytes());
+        }
+        Bind::Custom { .. } | Bind::Array { .. } => return Err(Error::InvalidCursor),
+    }
+    Ok(())
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if self.0.len() < len {
+            return Err(Error::InvalidCursor);
+        }
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    fn bytes(&mut self) -> Result<Vec<u8>, Error> {
+        let len = u32::from_be_bytes(self.array()?) as usize;
+        Ok(self.take(len)?.to_vec())
+    }
+
+    fn string(&mut self) -> Result<String, Error> {
+        String::from_utf8(self.bytes()?).map_err(|_| Error::InvalidCursor)
+    }
+}
+
+fn decode_value(reader: &mut Reader<'_>) -> Result<Option<Bind>, Error> {
+    let bind = match reader.take(1)?[0] {
+        0 => return Ok(None),
+        1 => Bind::String(reader.string()?),
+        2 => Bind::I32(i32::from_be_bytes(reader.array()?)),
+        3 => Bind::U64(u64::from_be_bytes(reader.array()?)),
+        4 => Bind::I16(i16::from_be_bytes(reader.array()?)),
+        5 => Bind::I64(i64::from_be_bytes(reader.array()?)),
+        6 => match reader.take(1)?[0] {
+            0 => Bind::Bool(false),
+            1 => Bind::Bool(true),
+            _ => return Err(Error::InvalidCursor),
+        },
+        7 => Bind::F32(f32::from_bits(u32::from_be_bytes(reader.array()?))),
+        8 => Bind::F64(f64::from_bits(u64::from_be_bytes(reader.array()?))),
+        9 => Bind::Numeric(reader.string()?),
+        10 => Bind::Bytes(reader.bytes()?),
+        11 => Bind::Date(i32::from_be_bytes(reader.array()?)),
+        12 => Bind::Time(i64::from_be_bytes(reader.array()?)),
+        13 => Bind::Timestamp(i64::from_be_bytes(reader.array()?)),
+        14 => Bind::Timestamptz(i64::from_be_bytes(reader.array()?)),
+        15 => Bind::Uuid(reader.array()?),
+        16 => Bind::Json(reader.string()?),
+        17 => Bind::Jsonb(reader.string()?),
+        18 => Bind::Interval {
+            months: i32::from_be_bytes(reader.array()?),
+            days: i32::from_be_bytes(reader.array()?),
+            micros: i64::from_be_bytes(reader.array()?),
+        },
+        _ => return Err(Error::InvalidCursor),
+    };
+    Ok(Some(bind))
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+// URL safe base64 without padding
+fn base64_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity((bytes.len() * 4).div_ceil(3));
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (idx, byte)| {
+            n | u32::from(*byte) << (16 - 8 * idx)
+        });
+        for idx in 0..=chunk.len() {
+            out.push(BASE64_ALPHABET[(n >> (18 - 6 * idx) & 0x3f) as usize] as char);
+        }
+    }
+    out
+}
+
+fn base64_decode(token: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(token.len() * 3 / 4);
+    for chunk in token.as_bytes().chunks(4) {
+        if chunk.len() == 1 {
+            return None;
+        }
+        let mut n = 0u32;
+        for (idx, c) in chunk.iter().enumerate() {
+            let value = BASE64_ALPHABET.iter().position(|a| a == c)? as u32;
+            n |= value << (18 - 6 * idx);
+        }
+        for idx in 0..chunk.len() - 1 {
+            out.push((n >> (16 - 8 * idx)) as u8);
+        }
+    }
+    Some(out)
+}
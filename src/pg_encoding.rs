@@ -0,0 +1,123 @@
+// Binary encodings shared by the `postgres-types` and `sqlx-postgres` features.
+
+use std::convert::TryFrom;
+
+pub(crate) type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+// Postgres counts dates and timestamps from 2000-01-01
+pub(crate) const PG_EPOCH_DAYS: i32 = 10_957;
+pub(crate) const PG_EPOCH_MICROS: i64 = 946_684_800_000_000;
+
+// The OIDs of the builtin types that can be array elements, and of their array types
+const BUILTIN_OIDS: &[(&str, u32, u32)] = &[
+    ("bool", 16, 1000),
+    ("bytea", 17, 1001),
+    ("int8", 20, 1016),
+    ("int2", 21, 1005),
+    ("int4", 23, 1007),
+    ("text", 25, 1009),
+    ("json", 114, 199),
+    ("float4", 700, 1021),
+    ("float8", 701, 1022),
+    ("date", 1082, 1182),
+    ("time", 1083, 1183),
+    ("timestamp", 1114, 1115),
+    ("timestamptz", 1184, 1185),
+    ("interval", 1186, 1187),
+    ("numeric", 1700, 1231),
+    ("uuid", 2950, 2951),
+    ("jsonb", 3802, 3807),
+];
+
+/// The OIDs of the builtin type `name` and of its array type.
+pub(crate) fn builtin_oids(name: &str) -> Option<(u32, u32)> {
+    BUILTIN_OIDS
+        .iter()
+        .find(|(builtin, _, _)| *builtin == name)
+        .map(|(_, oid, array_oid)| (*oid, *array_oid))
+}
+
+// The binary array format: the number of dimensions, whether there are nulls and the element
+// type, then the length and lower bound of each dimension, followed by the elements prefixed
+// with their length.
+pub(crate) fn encode_array<T>(
+    element_oid: u32,
+    values: &[T],
+    buf: &mut Vec<u8>,
+    mut encode_element: impl FnMut(&T, &mut Vec<u8>) -> Result<(), BoxError>,
+) -> Result<(), BoxError> {
+    buf.extend_from_slice(&1i32.to_be_bytes());
+    buf.extend_from_slice(&0i32.to_be_bytes());
+    buf.extend_from_slice(&element_oid.to_be_bytes());
+    buf.extend_from_slice(&i32::try_from(values.len())?.to_be_bytes());
+    buf.extend_from_slice(&1i32.to_be_bytes());
+    for value in values {
+        let start = buf.len();
+        buf.extend_from_slice(&0i32.to_be_bytes());
+        encode_element(value, buf)?;
+        let len = i32::try_from(buf.len() - start - 4)?;
+        buf[start..start + 4].copy_from_slice(&len.to_be_bytes());
+    }
+    Ok(())
+}
+
+// The binary `numeric` format: the number of digits, the weight of the first digit, the
+// sign and the number of decimal digits after the point, followed by the digits in base
+// 10000.
+pub(crate) fn encode_numeric(text: &str, buf: &mut Vec<u8>) -> Result<(), BoxError> {
+    let (negative, unsigned) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+    let (int, frac) = match unsigned.find('.') {
+        Some(idx) => (&unsigned[..idx], &unsigned[idx + 1..]),
+        None => (unsigned, ""),
+    };
+    if int.is_empty() && frac.is_empty()
+        || !int.chars().chain(frac.chars()).all(|c| c.is_ascii_digit())
+    {
+        return Err(format!("invalid numeric `{}`", text).into());
+    }
+
+    let int = format!("{:0>width$}", int, width = int.len().div_ceil(4) * 4);
+    let frac_scale = frac.len();
+    let frac = format!("{:0<width$}", frac, width = frac.len().div_ceil(4) * 4);
+
+    let mut digits = int
+        .as_bytes()
+        .chunks(4)
+        .chain(frac.as_bytes().chunks(4))
+        .map(|chunk| {
+            chunk
+                .iter()
+                .fold(0i16, |acc, c| acc * 10 + i16::from(c - b'0'))
+        })
+        .collect::<Vec<_>>();
+    let mut weight = (int.len() / 4) as i16 - 1;
+
+    let leading_zeros = digits.iter().take_while(|digit| **digit == 0).count();
+    digits.drain(..leading_zeros);
+    weight -= leading_zeros as i16;
+    while digits.last() == Some(&0) {
+        digits.pop();
+    }
+    if digits.is_empty() {
+        weight = 0;
+    }
+
+    let sign: u16 = if negative && !digits.is_empty() {
+        0x4000
+    } else {
+        0
+    };
+
+    buf.extend_from_slice(&(digits.len() as i16).to_be_bytes());
+    buf.extend_from_slice(&weight.to_be_bytes());
+    buf.extend_from_slice(&sign.to_be_bytes());
+    buf.extend_from_slice(&(frac_scale as u16).to_be_bytes());
+    for digit in digits {
+        buf.extend_from_slice(&digit.to_be_bytes());
+    }
+
+    Ok(())
+}
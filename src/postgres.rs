@@ -0,0 +1,209 @@
+use crate::pg_encoding::{
+    builtin_oids, encode_array, encode_numeric, PG_EPOCH_DAYS, PG_EPOCH_MICROS,
+};
+use crate::{Bind, Binds};
+use bytes::{BufMut, BytesMut};
+use postgres_types::{Format, IsNull, Kind, ToSql, Type, WrongType};
+use std::convert::TryFrom;
+use std::error::Error;
+
+impl Bind {
+    /// The Postgres type the bind is sent as.
+    pub fn pg_type(&self) -> Type {
+        match self {
+            Bind::String(_) => Type::TEXT,
+            Bind::I32(_) => Type::INT4,
+            Bind::U64(_) => Type::INT8,
+            Bind::I16(_) => Type::INT2,
+            Bind::I64(_) => Type::INT8,
+            Bind::Bool(_) => Type::BOOL,
+            Bind::F32(_) => Type::FLOAT4,
+            Bind::F64(_) => Type::FLOAT8,
+            Bind::Numeric(_) => Type::NUMERIC,
+            Bind::Bytes(_) => Type::BYTEA,
+            Bind::Date(_) => Type::DATE,
+            Bind::Time(_) => Type::TIME,
+            Bind::Timestamp(_) => Type::TIMESTAMP,
+            Bind::Timestamptz(_) => Type::TIMESTAMPTZ,
+            Bind::Uuid(_) => Type::UUID,
+            Bind::Json(_) => Type::JSON,
+            Bind::Jsonb(_) => Type::JSONB,
+            Bind::Interval { .. } => Type::INTERVAL,
+            // The OID of a user defined type isn't known up front, so let the server infer it
+            Bind::Custom { .. } => Type::UNKNOWN,
+            Bind::Array { element_type, .. } => builtin_oids(element_type)
+                .and_then(|(_, array_oid)| Type::from_oid(array_oid))
+                .unwrap_or(Type::UNKNOWN),
+        }
+    }
+}
+
+// Postgres doesn't have unsigned integers so `U64` is sent as `INT8`, failing if it doesn't fit.
+impl ToSql for Bind {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        match self {
+            Bind::String(s) => s.to_sql(ty, out),
+            Bind::I32(n) => n.to_sql(ty, out),
+            Bind::U64(n) => i64::try_from(*n)?.to_sql(ty, out),
+            Bind::I16(n) => n.to_sql(ty, out),
+            Bind::I64(n) => n.to_sql(ty, out),
+            Bind::Bool(b) => b.to_sql(ty, out),
+            Bind::F32(n) => n.to_sql(ty, out),
+            Bind::F64(n) => n.to_sql(ty, out),
+            Bind::Bytes(bytes) => bytes.to_sql(ty, out),
+            // Sent in the text format, see `encode_format`
+            Bind::Numeric(s) => {
+                out.put_slice(s.as_bytes());
+                Ok(IsNull::No)
+            }
+            Bind::Date(days) => {
+                let days = days.checked_sub(PG_EPOCH_DAYS).ok_or("date out of range")?;
+                out.put_i32(days);
+                Ok(IsNull::No)
+            }
+            Bind::Time(micros) => {
+                out.put_i64(*micros);
+                Ok(IsNull::No)
+            }
+            Bind::Timestamp(micros) | Bind::Timestamptz(micros) => {
+                let micros = micros
+                    .checked_sub(PG_EPOCH_MICROS)
+                    .ok_or("timestamp out of range")?;
+                out.put_i64(micros);
+                Ok(IsNull::No)
+            }
+            Bind::Uuid(bytes) => {
+                out.put_slice(bytes);
+                Ok(IsNull::No)
+            }
+            Bind::Json(s) => {
+                out.put_slice(s.as_bytes());
+                Ok(IsNull::No)
+            }
+            Bind::Jsonb(s) => {
+                // The jsonb format version
+                out.put_u8(1);
+                out.put_slice(s.as_bytes());
+                Ok(IsNull::No)
+            }
+            Bind::Interval {
+                months,
+                days,
+                micros,
+            } => {
+                out.put_i64(*micros);
+                out.put_i32(*days);
+                out.put_i32(*months);
+                Ok(IsNull::No)
+            }
+            Bind::Custom { bytes, .. } => {
+                out.put_slice(bytes);
+                Ok(IsNull::No)
+            }
+            // The element type comes from `ty` so arrays of user defined types work too
+            Bind::Array { values, .. } => {
+                let member = match ty.kind() {
+                    Kind::Array(member) => member,
+                    _ => return Err(Box::new(WrongType::new::<Bind>(ty.clone()))),
+                };
+                let mut buf = Vec::new();
+                encode_array(member.oid(), values, &mut buf, |value, buf| {
+                    // Elements are always binary
+                    if let Bind::Numeric(s) = value {
+                        return encode_numeric(s, buf);
+                    }
+                    let mut element = BytesMut::new();
+                    value.to_sql(member, &mut element)?;
+                    buf.extend_from_slice(&element);
+                    Ok(())
+                })?;
+                out.put_slice(&buf);
+                Ok(IsNull::No)
+            }
+        }
+    }
+
+    // User defined types are checked by name in `to_sql_checked`
+    fn accepts(ty: &Type) -> bool {
+        !matches!(ty.kind(), Kind::Simple)
+            || String::accepts(ty)
+            || i16::accepts(ty)
+            || i32::accepts(ty)
+            || i64::accepts(ty)
+            || bool::accepts(ty)
+            || f32::accepts(ty)
+            || f64::accepts(ty)
+            || Vec::<u8>::accepts(ty)
+            || matches!(
+                *ty,
+                Type::NUMERIC
+                    | Type::DATE
+                    | Type::TIME
+                    | Type::TIMESTAMP
+                    | Type::TIMESTAMPTZ
+                    | Type::UUID
+                    | Type::JSON
+                    | Type::JSONB
+            )
+    }
+
+    fn encode_format(&self, _ty: &Type) -> Format {
+        match self {
+            Bind::Numeric(_) => Format::Text,
+            _ => Format::Binary,
+        }
+    }
+
+    // `accepts` covers every variant, so check against the variant actually being sent
+    fn to_sql_checked(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        match self {
+            Bind::String(s) => s.to_sql_checked(ty, out),
+            // Postgres infers `bigint` for untyped parameters like `LIMIT $1`, so smaller integers
+            // are widened to whatever integer type the statement expects
+            Bind::I32(n) if *ty == Type::INT8 => i64::from(*n).to_sql_checked(ty, out),
+            Bind::I32(n) => n.to_sql_checked(ty, out),
+            Bind::U64(n) => i64::try_from(*n)?.to_sql_checked(ty, out),
+            Bind::I16(n) if *ty == Type::INT8 => i64::from(*n).to_sql_checked(ty, out),
+            Bind::I16(n) if *ty == Type::INT4 => i32::from(*n).to_sql_checked(ty, out),
+            Bind::I16(n) => n.to_sql_checked(ty, out),
+            Bind::I64(n) => n.to_sql_checked(ty, out),
+            Bind::Bool(b) => b.to_sql_checked(ty, out),
+            Bind::F32(n) => n.to_sql_checked(ty, out),
+            Bind::F64(n) => n.to_sql_checked(ty, out),
+            Bind::Bytes(bytes) => bytes.to_sql_checked(ty, out),
+            Bind::Custom { type_name, .. } if ty.name() != *type_name => {
+                Err(Box::new(WrongType::new::<Bind>(ty.clone())))
+            }
+            Bind::Custom { .. } => self.to_sql(ty, out),
+            Bind::Array { element_type, .. } => match ty.kind() {
+                Kind::Array(member) if member.name() == *element_type => self.to_sql(ty, out),
+                _ => Err(Box::new(WrongType::new::<Bind>(ty.clone()))),
+            },
+            _ if *ty != self.pg_type() => Err(Box::new(WrongType::new::<Bind>(ty.clone()))),
+            _ => self.to_sql(ty, out),
+        }
+    }
+}
+
+impl Binds {
+    /// The remaining binds as parameters for `postgres` and `tokio-postgres`.
+    pub fn as_params(&self) -> Vec<&(dyn ToSql + Sync)> {
+        self.as_slice()
+            .iter()
+            .map(|bind| bind as &(dyn ToSql + Sync))
+            .collect()
+    }
+
+    /// The types of the remaining binds, for `prepare_typed` and `query_typed`.
+    pub fn param_types(&self) -> Vec<Type> {
+        self.as_slice().iter().map(Bind::pg_type).collect()
+    }
+}
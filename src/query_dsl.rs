@@ -10,6 +10,10 @@ pub trait QueryDsl<T> {
 
     fn or_filter(self, filter: impl Into<Filter>) -> Query<T>;
 
+    fn filter_opt(self, filter: Option<impl Into<Filter>>) -> Query<T>;
+
+    fn filter_if(self, condition: bool, filter: impl Into<Filter>) -> Query<T>;
+
     fn join<K>(self, join: impl Into<JoinOn<K>>) -> Query<T>;
 
     fn inner_join<K>(self, join: impl Into<JoinOn<K>>) -> Query<T>;
@@ -56,7 +60,15 @@ pub trait QueryDsl<T> {
 
     fn explain_analyze(self) -> Query<T>;
 
+    /// Merges `other` into this query with the default `MergeOptions`. If both queries join the
+    /// same table differently the join of this query is kept, use `merge_with` to change that.
     fn merge<K>(self, other: impl Into<Query<K>>) -> Query<T>;
+
+    fn merge_with<K>(
+        self,
+        other: impl Into<Query<K>>,
+        options: MergeOptions,
+    ) -> Result<Query<T>, Error>;
 }
 
 impl<T, K> QueryDsl<K> for T
@@ -94,6 +106,21 @@ where
         query
     }
 
+    fn filter_opt(self, filter: Option<impl Into<Filter>>) -> Query<K> {
+        match filter {
+            Some(filter) => self.filter(filter),
+            None => self.into(),
+        }
+    }
+
+    fn filter_if(self, condition: bool, filter: impl Into<Filter>) -> Query<K> {
+        if condition {
+            self.filter(filter)
+        } else {
+            self.into()
+        }
+    }
+
     fn inner_join<J>(self, join: impl Into<JoinOn<J>>) -> Query<K> {
         let mut query = self.into();
         query.add_join(join.into().cast_to::<K>(), JoinKind::Inner);
@@ -260,19 +287,15 @@ where
     }
 
     fn merge<J>(self, other: impl Into<Query<J>>) -> Query<K> {
-        let mut lhs = self.into();
-        let rhs = other.into();
-
-        lhs.filter = match (lhs.filter, rhs.filter) {
-            (Some(a), Some(b)) => Some(Filter::And(Box::new(a), Box::new(b))),
-            (Some(a), None) => Some(a),
-            (None, Some(b)) => Some(b),
-            (None, None) => None,
-        };
-
-        lhs.joins.extend(rhs.joins.cast_to::<K>());
-        lhs.ctes.extend(rhs.ctes.cast_to::<K>());
+        self.merge_with(other, MergeOptions::default())
+            .expect("the default merge options never conflict")
+    }
 
-        lhs
+    fn merge_with<J>(
+        self,
+        other: impl Into<Query<J>>,
+        options: MergeOptions,
+    ) -> Result<Query<K>, Error> {
+        self.into().merge_query(other.into(), options)
     }
 }
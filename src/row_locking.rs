@@ -1,9 +1,11 @@
 use crate::binds::BindCount;
 use crate::binds::{BindsInternal, CollectBinds};
+use crate::dialect::Feature;
 use crate::write_sql::WriteSql;
+use crate::Error;
 use std::fmt::{self, Write};
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct RowLocking {
     pub for_update: bool,
     pub skip_locked: bool,
@@ -25,6 +27,30 @@ impl RowLocking {
         }
     }
 
+    pub fn is_locking(&self) -> bool {
+        self.for_update
+            || self.skip_locked
+            || self.for_key_share
+            || self.for_no_key_update
+            || self.for_share
+            || self.no_wait
+    }
+
+    pub fn check(&self) -> Result<(), Error> {
+        let modes = [
+            self.for_update,
+            self.for_no_key_update,
+            self.for_share,
+            self.for_key_share,
+        ];
+
+        if modes.iter().filter(|mode| **mode).count() > 1 || (self.no_wait && self.skip_locked) {
+            Err(Error::ConflictingRowLocking)
+        } else {
+            Ok(())
+        }
+    }
+
     pub fn or(self, other: RowLocking) -> Self {
         Self {
             for_update: self.for_update || other.for_update,
@@ -42,28 +68,34 @@ impl CollectBinds for RowLocking {
 }
 
 impl WriteSql for &RowLocking {
-    fn write_sql<W: Write>(self, f: &mut W, _: &mut BindCount) -> fmt::Result {
+    fn write_sql<W: Write>(self, f: &mut W, bind_count: &mut BindCount) -> fmt::Result {
         if self.for_update {
+            bind_count.require(Feature::ForUpdate)?;
             write!(f, " FOR UPDATE")?;
         }
 
         if self.for_no_key_update {
+            bind_count.require(Feature::ForNoKeyUpdate)?;
             write!(f, " FOR NO KEY UPDATE")?;
         }
 
         if self.for_share {
+            bind_count.require(Feature::ForShare)?;
             write!(f, " FOR SHARE")?;
         }
 
         if self.for_key_share {
+            bind_count.require(Feature::ForKeyShare)?;
             write!(f, " FOR KEY SHARE")?;
         }
 
         if self.no_wait {
-            write!(f, " NO WAIT")?;
+            bind_count.require(Feature::NoWait)?;
+            write!(f, " NOWAIT")?;
         }
 
         if self.skip_locked {
+            bind_count.require(Feature::SkipLocked)?;
             write!(f, " SKIP LOCKED")?;
         }
 
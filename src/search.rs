@@ -0,0 +1,70 @@
+use crate::sql_types::{CompatibleWith, Float, Text, TsQuery, TsVector};
+use crate::{BinOp, Expr, Filter, IntoExpr, TypedExpr};
+
+/// Postgres full text search operators.
+pub trait TsVectorExprDsl: Sized {
+    /// `self @@ query`
+    fn matches<Q>(self, query: Q) -> Filter
+    where
+        Q: IntoExpr<SqlType = TsQuery>;
+}
+
+impl<T> TsVectorExprDsl for T
+where
+    T: IntoExpr<SqlType = TsVector>,
+{
+    fn matches<Q>(self, query: Q) -> Filter
+    where
+        Q: IntoExpr<SqlType = TsQuery>,
+    {
+        Filter::BinOp {
+            lhs: self.into_expr(),
+            op: BinOp::TsMatch,
+            rhs: query.into_expr(),
+        }
+    }
+}
+
+// The config is bound, so it has to be cast for Postgres to pick the `regconfig` overload
+fn function_with_config<T>(name: &'static str, config: &str, arg: Expr) -> TypedExpr<T> {
+    let config = Expr::Cast {
+        expr: Box::new(config.into_expr()),
+        type_name: "regconfig",
+    };
+    TypedExpr::new(Expr::Function {
+        name,
+        args: vec![config, arg],
+    })
+}
+
+/// `to_tsvector(config, document)`
+pub fn to_tsvector<T>(config: &str, document: T) -> TypedExpr<TsVector>
+where
+    T: IntoExpr,
+    T::SqlType: CompatibleWith<Text>,
+{
+    function_with_config("to_tsvector", config, document.into_expr())
+}
+
+/// `plainto_tsquery(config, query)`, which ignores any operators in `query`.
+pub fn plainto_tsquery(config: &str, query: &str) -> TypedExpr<TsQuery> {
+    function_with_config("plainto_tsquery", config, query.into_expr())
+}
+
+/// `websearch_to_tsquery(config, query)`, which understands quotes, `or` and `-` like search
+/// engines do.
+pub fn websearch_to_tsquery(config: &str, query: &str) -> TypedExpr<TsQuery> {
+    function_with_config("websearch_to_tsquery", config, query.into_expr())
+}
+
+/// `ts_rank(vector, query)`
+pub fn ts_rank<V, Q>(vector: V, query: Q) -> TypedExpr<Float>
+where
+    V: IntoExpr<SqlType = TsVector>,
+    Q: IntoExpr<SqlType = TsQuery>,
+{
+    TypedExpr::new(Expr::Function {
+        name: "ts_rank",
+        args: vec![vector.into_expr(), query.into_expr()],
+    })
+}
@@ -1,6 +1,6 @@
-use crate::binds::BindCount;
-use crate::{Column, Table, WriteSql};
-use itertools::{Itertools, Position};
+use crate::binds::{BindCount, BindsInternal, CollectBinds};
+use crate::validate::{CollectTableRefs, TableRef};
+use crate::{Column, Expr, Table, WriteSql};
 use std::fmt::{self, Write};
 
 pub fn star() -> Selection {
@@ -11,7 +11,7 @@ pub fn count(selection: impl Into<Selection>) -> Select {
     Select::CountStar(selection.into())
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Select {
     CountStar(Selection),
     Simple(Selection),
@@ -22,6 +22,22 @@ impl Select {
     pub fn raw(sql: &str) -> Selection {
         Selection::Raw(sql.to_string())
     }
+
+    // Whether the query returns a single row because of an aggregate in its select list
+    pub(crate) fn may_aggregate(&self) -> bool {
+        match self {
+            Select::CountStar(_) => true,
+            Select::Simple(selection) => selection.may_aggregate(),
+            Select::List(selections) => selections.iter().any(Selection::may_aggregate),
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        match self {
+            Select::CountStar(_) | Select::Simple(_) => false,
+            Select::List(selections) => selections.is_empty(),
+        }
+    }
 }
 
 impl WriteSql for &Select {
@@ -42,14 +58,38 @@ impl WriteSql for &Select {
     }
 }
 
-#[derive(Debug, Clone)]
+impl CollectTableRefs for Select {
+    fn collect_table_refs<'a>(&'a self, refs: &mut Vec<TableRef<'a>>) {
+        match self {
+            Select::CountStar(inner) | Select::Simple(inner) => inner.collect_table_refs(refs),
+            Select::List(selections) => {
+                for selection in selections {
+                    selection.collect_table_refs(refs);
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Selection {
     Star,
     TableStar(Table),
     Column(Column),
+    Expr { expr: Expr, alias: Option<String> },
     Raw(String),
 }
 
+impl Selection {
+    fn may_aggregate(&self) -> bool {
+        match self {
+            Selection::Star | Selection::TableStar(_) | Selection::Column(_) => false,
+            Selection::Expr { expr, alias: _ } => expr.may_aggregate(),
+            Selection::Raw(_) => true,
+        }
+    }
+}
+
 impl From<Selection> for Select {
     fn from(selection: Selection) -> Self {
         Select::Simple(selection)
@@ -66,16 +106,60 @@ impl WriteSql for &Selection {
                 write!(f, ".*")
             }
             Selection::Column(col) => col.write_sql(f, bind_count),
+            Selection::Expr { expr, alias } => {
+                expr.write_sql(f, bind_count)?;
+                if let Some(alias) = alias {
+                    write!(f, " AS ")?;
+                    bind_count.write_identifier(f, alias)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl CollectTableRefs for Selection {
+    fn collect_table_refs<'a>(&'a self, refs: &mut Vec<TableRef<'a>>) {
+        match self {
+            Selection::Star | Selection::Raw(_) => {}
+            Selection::TableStar(table) => refs.push(TableRef::Table(table)),
+            Selection::Column(col) => refs.push(TableRef::Column(col)),
+            Selection::Expr { expr, alias: _ } => expr.collect_table_refs(refs),
+        }
+    }
+}
+
+impl CollectBinds for Select {
+    fn collect_binds(&self, binds: &mut BindsInternal) {
+        match self {
+            Select::CountStar(inner) | Select::Simple(inner) => inner.collect_binds(binds),
+            Select::List(selections) => {
+                for selection in selections {
+                    selection.collect_binds(binds);
+                }
+            }
+        }
+    }
+}
+
+impl CollectBinds for Selection {
+    fn collect_binds(&self, binds: &mut BindsInternal) {
+        match self {
+            Selection::Star
+            | Selection::TableStar(_)
+            | Selection::Column(_)
+            | Selection::Raw(_) => {}
+            Selection::Expr { expr, alias: _ } => expr.collect_binds(binds),
         }
     }
 }
 
-impl<T> Into<Select> for (T,)
+impl<T> From<(T,)> for Select
 where
     T: Into<Selection>,
 {
-    fn into(self) -> Select {
-        Select::Simple(self.0.into())
+    fn from(selection: (T,)) -> Self {
+        Select::Simple(selection.0.into())
     }
 }
 
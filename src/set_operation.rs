@@ -0,0 +1,437 @@
+use crate::binds::{BindCount, BindsInternal, CollectBinds};
+use crate::dialect::{Dialect, Feature, Postgres, RenderMode};
+use crate::order::Ordering;
+use crate::{check_bind_count, Binds, Error, Limit, Offset, Order, QueryWithSelect, WriteSql};
+use std::fmt::{self, Write};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum SetOperator {
+    Union,
+    UnionAll,
+    UnionDistinct,
+    Intersect,
+    IntersectAll,
+    IntersectDistinct,
+    Except,
+    ExceptAll,
+    ExceptDistinct,
+}
+
+impl SetOperator {
+    // INTERSECT binds tighter than UNION and EXCEPT, which share the same precedence
+    fn precedence(self) -> u8 {
+        match self {
+            SetOperator::Intersect | SetOperator::IntersectAll | SetOperator::IntersectDistinct => {
+                2
+            }
+            SetOperator::Union
+            | SetOperator::UnionAll
+            | SetOperator::UnionDistinct
+            | SetOperator::Except
+            | SetOperator::ExceptAll
+            | SetOperator::ExceptDistinct => 1,
+        }
+    }
+}
+
+impl SetOperator {
+    fn feature(self) -> Option<Feature> {
+        match self {
+            SetOperator::Union | SetOperator::UnionAll => None,
+            SetOperator::Intersect | SetOperator::Except => None,
+            SetOperator::UnionDistinct => Some(Feature::UnionDistinct),
+            SetOperator::IntersectAll => Some(Feature::IntersectAll),
+            SetOperator::IntersectDistinct => Some(Feature::IntersectDistinct),
+            SetOperator::ExceptAll => Some(Feature::ExceptAll),
+            SetOperator::ExceptDistinct => Some(Feature::ExceptDistinct),
+        }
+    }
+}
+
+impl WriteSql for SetOperator {
+    fn write_sql<W: Write>(self, f: &mut W, bind_count: &mut BindCount) -> fmt::Result {
+        if let Some(feature) = self.feature() {
+            bind_count.require(feature)?;
+        }
+
+        match self {
+            SetOperator::Union => write!(f, " UNION "),
+            SetOperator::UnionAll => write!(f, " UNION ALL "),
+            SetOperator::UnionDistinct => write!(f, " UNION DISTINCT "),
+            SetOperator::Intersect => write!(f, " INTERSECT "),
+            SetOperator::IntersectAll => write!(f, " INTERSECT ALL "),
+            SetOperator::IntersectDistinct => write!(f, " INTERSECT DISTINCT "),
+            SetOperator::Except => write!(f, " EXCEPT "),
+            SetOperator::ExceptAll => write!(f, " EXCEPT ALL "),
+            SetOperator::ExceptDistinct => write!(f, " EXCEPT DISTINCT "),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SetOperation<T> {
+    node: SetOperationNode<T>,
+    order: Option<Order>,
+    limit: Option<Limit>,
+    offset: Option<Offset>,
+}
+
+#[derive(Debug, Clone)]
+enum SetOperationNode<T> {
+    Select(Box<QueryWithSelect<T>>),
+    Op {
+        op: SetOperator,
+        lhs: Box<SetOperation<T>>,
+        rhs: Box<SetOperation<T>>,
+    },
+}
+
+impl<T> PartialEq for SetOperation<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.node == other.node
+            && self.order == other.order
+            && self.limit == other.limit
+            && self.offset == other.offset
+    }
+}
+
+impl<T> PartialEq for SetOperationNode<T> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (SetOperationNode::Select(lhs), SetOperationNode::Select(rhs)) => lhs == rhs,
+            (
+                SetOperationNode::Op { op, lhs, rhs },
+                SetOperationNode::Op {
+                    op: other_op,
+                    lhs: other_lhs,
+                    rhs: other_rhs,
+                },
+            ) => op == other_op && lhs == other_lhs && rhs == other_rhs,
+            _ => false,
+        }
+    }
+}
+
+impl<T> From<QueryWithSelect<T>> for SetOperation<T> {
+    fn from(query: QueryWithSelect<T>) -> Self {
+        SetOperation {
+            node: SetOperationNode::Select(Box::new(query)),
+            order: None,
+            limit: None,
+            offset: None,
+        }
+    }
+}
+
+impl<T> SetOperation<T> {
+    pub fn to_sql(self) -> (String, Binds) {
+        self.render(&Postgres)
+            .expect("Postgres supports everything, and only `check` rejects set operations")
+    }
+
+    pub fn try_to_sql(self) -> Result<(String, Binds), Error> {
+        self.to_sql_with(&Postgres)
+    }
+
+    pub fn to_sql_with(self, dialect: &dyn Dialect) -> Result<(String, Binds), Error> {
+        self.check()?;
+        let (sql, binds) = self.render(dialect)?;
+        check_bind_count(&binds, dialect)?;
+        Ok((sql, binds))
+    }
+
+    pub(crate) fn render(&self, dialect: &dyn Dialect) -> Result<(String, Binds), Error> {
+        let mut bind_count = BindCount::new(dialect);
+        let mut sql = String::new();
+
+        if let Err(err) = self.write_sql(&mut sql, &mut bind_count) {
+            return Err(bind_count.into_error(err));
+        }
+
+        let mut binds = BindsInternal::with_capacity(bind_count.count(), dialect);
+        self.collect_binds(&mut binds);
+
+        Ok((sql, Binds::from(binds)))
+    }
+
+    /// Validate every query in the set operation. See `QueryWithSelect::validate`.
+    pub fn validate(&self) -> Result<(), Error> {
+        match &self.node {
+            SetOperationNode::Select(query) => query.validate(),
+            SetOperationNode::Op { op: _, lhs, rhs } => {
+                lhs.validate()?;
+                rhs.validate()
+            }
+        }
+    }
+
+    // The `ORDER BY` of a set operation can only refer to columns of the result. `to_sql` writes
+    // other orderings anyway and leaves it to the database to reject them.
+    pub(crate) fn check(&self) -> Result<(), Error> {
+        if let Some(order) = &self.order {
+            let result_columns_only = order.orderings().iter().all(|ordering| {
+                matches!(ordering, Ordering::Raw(_)) || ordering.column().is_some()
+            });
+            if !result_columns_only {
+                return Err(Error::UnsupportedSetOperationOrder);
+            }
+        }
+
+        match &self.node {
+            SetOperationNode::Select(query) => query.check(),
+            SetOperationNode::Op { op: _, lhs, rhs } => {
+                lhs.check()?;
+                rhs.check()
+            }
+        }
+    }
+
+    pub fn cast_to<K>(self) -> SetOperation<K> {
+        let SetOperation {
+            node,
+            order,
+            limit,
+            offset,
+        } = self;
+
+        let node = match node {
+            SetOperationNode::Select(query) => {
+                SetOperationNode::Select(Box::new(query.cast_to::<K>()))
+            }
+            SetOperationNode::Op { op, lhs, rhs } => SetOperationNode::Op {
+                op,
+                lhs: Box::new(lhs.cast_to::<K>()),
+                rhs: Box::new(rhs.cast_to::<K>()),
+            },
+        };
+
+        SetOperation {
+            node,
+            order,
+            limit,
+            offset,
+        }
+    }
+
+    fn combine<K>(self, op: SetOperator, other: impl Into<SetOperation<K>>) -> SetOperation<T> {
+        SetOperation {
+            node: SetOperationNode::Op {
+                op,
+                lhs: Box::new(self),
+                rhs: Box::new(other.into().cast_to::<T>()),
+            },
+            order: None,
+            limit: None,
+            offset: None,
+        }
+    }
+
+    pub fn union<K>(self, other: impl Into<SetOperation<K>>) -> SetOperation<T> {
+        self.combine(SetOperator::Union, other)
+    }
+
+    pub fn union_all<K>(self, other: impl Into<SetOperation<K>>) -> SetOperation<T> {
+        self.combine(SetOperator::UnionAll, other)
+    }
+
+    pub fn union_distinct<K>(self, other: impl Into<SetOperation<K>>) -> SetOperation<T> {
+        self.combine(SetOperator::UnionDistinct, other)
+    }
+
+    pub fn intersect<K>(self, other: impl Into<SetOperation<K>>) -> SetOperation<T> {
+        self.combine(SetOperator::Intersect, other)
+    }
+
+    pub fn intersect_all<K>(self, other: impl Into<SetOperation<K>>) -> SetOperation<T> {
+        self.combine(SetOperator::IntersectAll, other)
+    }
+
+    pub fn intersect_distinct<K>(self, other: impl Into<SetOperation<K>>) -> SetOperation<T> {
+        self.combine(SetOperator::IntersectDistinct, other)
+    }
+
+    pub fn except<K>(self, other: impl Into<SetOperation<K>>) -> SetOperation<T> {
+        self.combine(SetOperator::Except, other)
+    }
+
+    pub fn except_all<K>(self, other: impl Into<SetOperation<K>>) -> SetOperation<T> {
+        self.combine(SetOperator::ExceptAll, other)
+    }
+
+    pub fn except_distinct<K>(self, other: impl Into<SetOperation<K>>) -> SetOperation<T> {
+        self.combine(SetOperator::ExceptDistinct, other)
+    }
+
+    pub fn order_by(mut self, order: impl Into<Order>) -> Self {
+        self.order = Some(order.into());
+        self
+    }
+
+    pub fn limit(mut self, limit: impl Into<Limit>) -> Self {
+        self.limit = Some(limit.into());
+        self
+    }
+
+    pub fn offset(mut self, offset: impl Into<Offset>) -> Self {
+        self.offset = Some(offset.into());
+        self
+    }
+
+    pub fn remove_order_by(mut self) -> Self {
+        self.order = None;
+        self
+    }
+
+    pub fn remove_limit(mut self) -> Self {
+        self.limit = None;
+        self
+    }
+
+    pub fn remove_offset(mut self) -> Self {
+        self.offset = None;
+        self
+    }
+
+    fn has_trailing_clauses(&self) -> bool {
+        self.order.is_some() || self.limit.is_some() || self.offset.is_some()
+    }
+
+    // Dialects without nested set operations are assumed to evaluate the operators left to
+    // right, so a nested rhs is wrapped even when it binds tighter
+    fn needs_parens(&self, parent: SetOperator, is_rhs: bool, by_precedence: bool) -> bool {
+        if self.has_trailing_clauses() {
+            return true;
+        }
+
+        match &self.node {
+            SetOperationNode::Select(query) => query.needs_parens_in_set_operation(),
+            SetOperationNode::Op { op, .. } => {
+                op.precedence() < parent.precedence()
+                    || (is_rhs && (op.precedence() == parent.precedence() || !by_precedence))
+            }
+        }
+    }
+
+    fn write_operand<W: Write>(
+        &self,
+        f: &mut W,
+        bind_count: &mut BindCount,
+        parens: bool,
+    ) -> fmt::Result {
+        if parens {
+            write_parenthesized(f, bind_count, |f, bind_count| self.write_sql(f, bind_count))
+        } else {
+            self.write_sql(f, bind_count)
+        }
+    }
+}
+
+fn write_parenthesized<W: Write>(
+    f: &mut W,
+    bind_count: &mut BindCount,
+    write: impl FnOnce(&mut W, &mut BindCount) -> fmt::Result,
+) -> fmt::Result {
+    match bind_count.render_mode(Feature::NestedSetOperations)? {
+        RenderMode::Native => {
+            write!(f, "(")?;
+            write(f, bind_count)?;
+            write!(f, ")")
+        }
+        RenderMode::Emulated => {
+            write!(f, "SELECT * FROM (")?;
+            write(f, bind_count)?;
+            write!(f, ") AS ")?;
+            bind_count.write_identifier(f, "__operand")
+        }
+    }
+}
+
+impl<T> WriteSql for &SetOperation<T> {
+    fn write_sql<W: Write>(self, f: &mut W, bind_count: &mut BindCount) -> fmt::Result {
+        match &self.node {
+            SetOperationNode::Select(query) => {
+                if self.has_trailing_clauses() && query.needs_parens_in_set_operation() {
+                    write_parenthesized(f, bind_count, |f, bind_count| {
+                        query.write_sql(f, bind_count)
+                    })?;
+                } else {
+                    query.write_sql(f, bind_count)?;
+                }
+            }
+            SetOperationNode::Op { op, lhs, rhs } => {
+                let by_precedence = bind_count.supports(Feature::NestedSetOperations);
+                lhs.write_operand(f, bind_count, lhs.needs_parens(*op, false, by_precedence))?;
+                op.write_sql(f, bind_count)?;
+                rhs.write_operand(f, bind_count, rhs.needs_parens(*op, true, by_precedence))?;
+            }
+        }
+
+        if let Some(order) = &self.order {
+            write!(f, " ORDER BY ")?;
+            for (idx, ordering) in order.orderings().iter().enumerate() {
+                if idx > 0 {
+                    write!(f, ", ")?;
+                }
+                write_result_ordering(ordering, f, bind_count)?;
+            }
+        }
+
+        if let Some(limit) = &self.limit {
+            write!(f, " LIMIT ")?;
+            limit.0.write_sql(f, bind_count)?;
+        }
+
+        if let Some(offset) = &self.offset {
+            if self.limit.is_none() {
+                bind_count.require(Feature::OffsetWithoutLimit)?;
+            }
+            write!(f, " OFFSET ")?;
+            offset.0.write_sql(f, bind_count)?;
+        }
+
+        Ok(())
+    }
+}
+
+// Columns are written without their table since they refer to the columns of the result. Other
+// expressions have been rejected by `check`, or are written as they are by `to_sql`.
+fn write_result_ordering<W: Write>(
+    ordering: &Ordering,
+    f: &mut W,
+    bind_count: &mut BindCount,
+) -> fmt::Result {
+    let col = match ordering.column() {
+        Some(col) => col,
+        None => return ordering.write_sql(f, bind_count),
+    };
+    if ordering.nulls_position().is_some() {
+        bind_count.require(Feature::NullsPosition)?;
+    }
+    ordering.write_sql_with(f, bind_count, |f, bind_count| {
+        bind_count.write_identifier(f, col.name)
+    })
+}
+
+impl<T> CollectBinds for SetOperation<T> {
+    fn collect_binds(&self, binds: &mut BindsInternal) {
+        match &self.node {
+            SetOperationNode::Select(query) => query.collect_binds(binds),
+            SetOperationNode::Op { op: _, lhs, rhs } => {
+                lhs.collect_binds(binds);
+                rhs.collect_binds(binds);
+            }
+        }
+
+        if let Some(order) = &self.order {
+            order.collect_binds(binds);
+        }
+
+        if let Some(limit) = &self.limit {
+            limit.0.collect_binds(binds);
+        }
+
+        if let Some(offset) = &self.offset {
+            offset.0.collect_binds(binds);
+        }
+    }
+}
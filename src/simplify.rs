@@ -0,0 +1,165 @@
+use crate::{BinOp, Expr, Filter};
+
+impl Filter {
+    /// Rewrites the filter into a shorter one with the same meaning.
+    ///
+    /// Nested `AND`s and `OR`s are flattened and duplicate operands removed, `TRUE` and `FALSE`
+    /// are folded away, double negations cancel out, and `x = a OR x = b` becomes
+    /// `x IN (a, b)`. The order of the remaining operands is kept.
+    pub fn simplify(self) -> Filter {
+        match self {
+            Filter::And(_, _) => {
+                let mut operands = Vec::new();
+                flatten(self, &mut operands, Junction::And);
+                simplify_junction(operands, Junction::And)
+            }
+            Filter::Or(_, _) => {
+                let mut operands = Vec::new();
+                flatten(self, &mut operands, Junction::Or);
+                let operands = merge_equalities(operands);
+                simplify_junction(operands, Junction::Or)
+            }
+            Filter::Not(inner) => match inner.simplify() {
+                Filter::True => Filter::False,
+                Filter::False => Filter::True,
+                Filter::Not(inner) => *inner,
+                inner => inner.not(),
+            },
+            other => other,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum Junction {
+    And,
+    Or,
+}
+
+impl Junction {
+    // The value that makes the whole junction that value, such as `FALSE` for `AND`
+    fn absorbing(self) -> Filter {
+        match self {
+            Junction::And => Filter::False,
+            Junction::Or => Filter::True,
+        }
+    }
+
+    fn identity(self) -> Filter {
+        match self {
+            Junction::And => Filter::True,
+            Junction::Or => Filter::False,
+        }
+    }
+
+    fn split(self, filter: Filter) -> Result<(Filter, Filter), Filter> {
+        match (self, filter) {
+            (Junction::And, Filter::And(lhs, rhs)) | (Junction::Or, Filter::Or(lhs, rhs)) => {
+                Ok((*lhs, *rhs))
+            }
+            (_, filter) => Err(filter),
+        }
+    }
+
+    fn join(self, lhs: Filter, rhs: Filter) -> Filter {
+        match self {
+            Junction::And => lhs.and(rhs),
+            Junction::Or => lhs.or(rhs),
+        }
+    }
+}
+
+// Collects the simplified operands of nested junctions of the same kind. Simplifying an operand
+// can produce another junction of the same kind, such as `NOT NOT (a AND b)`.
+fn flatten(filter: Filter, operands: &mut Vec<Filter>, junction: Junction) {
+    match junction.split(filter) {
+        Ok((lhs, rhs)) => {
+            flatten(lhs, operands, junction);
+            flatten(rhs, operands, junction);
+        }
+        Err(filter) => push_operands(filter.simplify(), operands, junction),
+    }
+}
+
+fn push_operands(filter: Filter, operands: &mut Vec<Filter>, junction: Junction) {
+    match junction.split(filter) {
+        Ok((lhs, rhs)) => {
+            push_operands(lhs, operands, junction);
+            push_operands(rhs, operands, junction);
+        }
+        Err(filter) => operands.push(filter),
+    }
+}
+
+fn simplify_junction(operands: Vec<Filter>, junction: Junction) -> Filter {
+    let absorbing = junction.absorbing();
+    let identity = junction.identity();
+
+    let mut kept: Vec<Filter> = Vec::with_capacity(operands.len());
+    for operand in operands {
+        if operand == absorbing {
+            return absorbing;
+        }
+        if operand != identity && !kept.contains(&operand) {
+            kept.push(operand);
+        }
+    }
+
+    kept.into_iter()
+        .fold(None, |acc, operand| match acc {
+            Some(acc) => Some(junction.join(acc, operand)),
+            None => Some(operand),
+        })
+        .unwrap_or(identity)
+}
+
+// Merges equalities with the same left hand side into one `IN` at the position of the first
+fn merge_equalities(operands: Vec<Filter>) -> Vec<Filter> {
+    let mut merged: Vec<Filter> = Vec::with_capacity(operands.len());
+
+    for operand in operands {
+        let (lhs, values) = match operand {
+            Filter::BinOp {
+                lhs,
+                op: BinOp::Eq,
+                rhs,
+            } if !matches!(rhs, Expr::Any(_)) => (lhs, vec![rhs]),
+            Filter::In { lhs, values } => (lhs, values),
+            other => {
+                merged.push(other);
+                continue;
+            }
+        };
+
+        let existing = merged.iter_mut().find_map(|filter| match filter {
+            Filter::In {
+                lhs: existing_lhs,
+                values,
+            } if *existing_lhs == lhs => Some(values),
+            _ => None,
+        });
+        match existing {
+            Some(existing) => {
+                for value in values {
+                    if !existing.contains(&value) {
+                        existing.push(value);
+                    }
+                }
+            }
+            None => merged.push(Filter::In { lhs, values }),
+        }
+    }
+
+    // Single equalities are kept as they were
+    merged
+        .into_iter()
+        .map(|filter| match filter {
+            Filter::In { lhs, mut values } if values.len() == 1 => Filter::BinOp {
+                lhs,
+                op: BinOp::Eq,
+                rhs: values.remove(0),
+            },
+            other => other,
+        })
+        .collect()
+}
@@ -1,3 +1,132 @@
+use std::marker::PhantomData;
+
 pub struct Integer;
 
 pub struct Text;
+
+pub struct SmallInt;
+
+pub struct BigInt;
+
+pub struct Bool;
+
+pub struct Float;
+
+pub struct Double;
+
+pub struct Numeric;
+
+pub struct Bytea;
+
+pub struct Date;
+
+pub struct Time;
+
+pub struct Timestamp;
+
+pub struct Timestamptz;
+
+pub struct Uuid;
+
+pub struct Json;
+
+pub struct Jsonb;
+
+pub struct Interval;
+
+pub struct TsVector;
+
+pub struct TsQuery;
+
+pub struct Nullable<T>(PhantomData<T>);
+
+pub struct Array<T>(PhantomData<T>);
+
+/// SQL types that can be the element type of an `Array`. `NAME` is the name of the type in
+/// Postgres.
+pub trait ArrayElement {
+    const NAME: &'static str;
+}
+
+macro_rules! impl_array_element {
+    ($($ty:ident => $name:literal,)*) => {
+        $(
+            impl ArrayElement for $ty {
+                const NAME: &'static str = $name;
+            }
+        )*
+    };
+}
+
+impl_array_element! {
+    Integer => "int4",
+    Text => "text",
+    SmallInt => "int2",
+    BigInt => "int8",
+    Bool => "bool",
+    Float => "float4",
+    Double => "float8",
+    Numeric => "numeric",
+    Bytea => "bytea",
+    Date => "date",
+    Time => "time",
+    Timestamp => "timestamp",
+    Timestamptz => "timestamptz",
+    Uuid => "uuid",
+    Json => "json",
+    Jsonb => "jsonb",
+    Interval => "interval",
+}
+
+/// SQL types that an `Interval` can be added to or subtracted from. `Output` is the type of the
+/// result.
+pub trait IntervalArithmetic {
+    type Output;
+}
+
+impl IntervalArithmetic for Date {
+    type Output = Timestamp;
+}
+
+impl IntervalArithmetic for Timestamp {
+    type Output = Timestamp;
+}
+
+impl IntervalArithmetic for Timestamptz {
+    type Output = Timestamptz;
+}
+
+impl IntervalArithmetic for Interval {
+    type Output = Interval;
+}
+
+impl<T: IntervalArithmetic> IntervalArithmetic for Nullable<T> {
+    type Output = Nullable<T::Output>;
+}
+
+/// Timestamp types that `AT TIME ZONE` converts between. `Output` is the other type.
+pub trait AtTimeZone {
+    type Output;
+}
+
+impl AtTimeZone for Timestamp {
+    type Output = Timestamptz;
+}
+
+impl AtTimeZone for Timestamptz {
+    type Output = Timestamp;
+}
+
+impl<T: AtTimeZone> AtTimeZone for Nullable<T> {
+    type Output = Nullable<T::Output>;
+}
+
+/// SQL types that can be compared with `T`. A type is compatible with itself and with its
+/// nullable counterpart.
+pub trait CompatibleWith<T> {}
+
+impl<T> CompatibleWith<T> for T {}
+
+impl<T> CompatibleWith<Nullable<T>> for T {}
+
+impl<T> CompatibleWith<T> for Nullable<T> {}
@@ -0,0 +1,131 @@
+use crate::datetime::{format_date, format_time, format_timestamp};
+use crate::{Bind, Binds, Error, QueryWithSelect, SetOperation, Sqlite};
+use rusqlite::types::ToSqlOutput;
+use rusqlite::{params_from_iter, Connection, Row, ToSql};
+use std::fmt;
+
+/// Error returned when running a query with `rusqlite`.
+#[derive(Debug)]
+pub enum SqliteError {
+    Query(Error),
+    Sqlite(rusqlite::Error),
+}
+
+impl fmt::Display for SqliteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SqliteError::Query(err) => write!(f, "{}", err),
+            SqliteError::Sqlite(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for SqliteError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SqliteError::Query(err) => Some(err),
+            SqliteError::Sqlite(err) => Some(err),
+        }
+    }
+}
+
+impl From<Error> for SqliteError {
+    fn from(err: Error) -> Self {
+        SqliteError::Query(err)
+    }
+}
+
+impl From<rusqlite::Error> for SqliteError {
+    fn from(err: rusqlite::Error) -> Self {
+        SqliteError::Sqlite(err)
+    }
+}
+
+// SQLite has no date and time types so they are sent as text, which its date functions
+// understand.
+impl ToSql for Bind {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        match self {
+            Bind::String(s) | Bind::Numeric(s) | Bind::Json(s) | Bind::Jsonb(s) => s.to_sql(),
+            Bind::I32(n) => n.to_sql(),
+            Bind::U64(n) => n.to_sql(),
+            Bind::I16(n) => n.to_sql(),
+            Bind::I64(n) => n.to_sql(),
+            Bind::Bool(b) => b.to_sql(),
+            Bind::F32(n) => Ok(ToSqlOutput::from(f64::from(*n))),
+            Bind::F64(n) => n.to_sql(),
+            Bind::Bytes(bytes) => bytes.to_sql(),
+            Bind::Uuid(bytes) => Ok(ToSqlOutput::from(&bytes[..])),
+            // Custom types are usually text, such as enum labels
+            Bind::Custom { bytes, .. } => match std::str::from_utf8(bytes) {
+                Ok(text) => text.to_sql(),
+                Err(_) => bytes.to_sql(),
+            },
+            Bind::Date(days) => Ok(ToSqlOutput::from(format_date(*days))),
+            Bind::Time(micros) => Ok(ToSqlOutput::from(format_time(*micros))),
+            Bind::Timestamp(micros) | Bind::Timestamptz(micros) => {
+                Ok(ToSqlOutput::from(format_timestamp(*micros)))
+            }
+            Bind::Array { .. } => Err(rusqlite::Error::ToSqlConversionFailure(
+                "SQLite doesn't support arrays".into(),
+            )),
+            Bind::Interval { .. } => Err(rusqlite::Error::ToSqlConversionFailure(
+                "SQLite doesn't support intervals".into(),
+            )),
+        }
+    }
+}
+
+impl<T> QueryWithSelect<T> {
+    /// Run the query on `conn` and map each row with `f`.
+    pub fn load<R, F>(self, conn: &Connection, f: F) -> Result<Vec<R>, SqliteError>
+    where
+        F: FnMut(&Row<'_>) -> rusqlite::Result<R>,
+    {
+        let (sql, binds) = self.to_sql_with(&Sqlite)?;
+        load(conn, &sql, binds, f)
+    }
+
+    /// Run the query on `conn` and return how many rows it produced.
+    pub fn execute(self, conn: &Connection) -> Result<usize, SqliteError> {
+        let (sql, binds) = self.to_sql_with(&Sqlite)?;
+        execute(conn, &sql, binds)
+    }
+}
+
+impl<T> SetOperation<T> {
+    /// Run the query on `conn` and map each row with `f`.
+    pub fn load<R, F>(self, conn: &Connection, f: F) -> Result<Vec<R>, SqliteError>
+    where
+        F: FnMut(&Row<'_>) -> rusqlite::Result<R>,
+    {
+        let (sql, binds) = self.to_sql_with(&Sqlite)?;
+        load(conn, &sql, binds, f)
+    }
+
+    /// Run the query on `conn` and return how many rows it produced.
+    pub fn execute(self, conn: &Connection) -> Result<usize, SqliteError> {
+        let (sql, binds) = self.to_sql_with(&Sqlite)?;
+        execute(conn, &sql, binds)
+    }
+}
+
+fn load<R, F>(conn: &Connection, sql: &str, binds: Binds, f: F) -> Result<Vec<R>, SqliteError>
+where
+    F: FnMut(&Row<'_>) -> rusqlite::Result<R>,
+{
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map(params_from_iter(binds), f)?;
+    Ok(rows.collect::<Result<_, _>>()?)
+}
+
+// `Statement::execute` refuses statements that return rows, so step through them instead.
+fn execute(conn: &Connection, sql: &str, binds: Binds) -> Result<usize, SqliteError> {
+    let mut stmt = conn.prepare(sql)?;
+    let mut rows = stmt.query(params_from_iter(binds))?;
+    let mut count = 0;
+    while rows.next()?.is_some() {
+        count += 1;
+    }
+    Ok(count)
+}
@@ -0,0 +1,295 @@
+#[cfg(any(feature = "sqlx-mysql", feature = "sqlx-sqlite"))]
+use crate::datetime::{format_date, format_time, format_timestamp};
+use crate::{Bind, Binds, Dialect, Error, QueryWithSelect, SetOperation};
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::query::{Query, QueryAs};
+use sqlx::{AssertSqlSafe, Database, Encode, FromRow, Type};
+#[cfg(any(feature = "sqlx-mysql", feature = "sqlx-sqlite"))]
+use std::convert::TryFrom;
+
+/// A `sqlx` database that queries can be converted for. Implemented for the databases enabled
+/// with the `sqlx-postgres`, `sqlx-mysql` and `sqlx-sqlite` features.
+pub trait SqlxDatabase: Database {
+    fn dialect() -> &'static dyn Dialect;
+
+    #[doc(hidden)]
+    fn encode_bind(bind: &Bind, buf: &mut Self::ArgumentBuffer) -> Result<IsNull, BoxDynError>;
+
+    #[doc(hidden)]
+    fn bind_type(bind: &Bind) -> Self::TypeInfo;
+}
+
+// The encoding of each variant depends on the database, so `Bind` defers to `SqlxDatabase`.
+impl<'q, DB: SqlxDatabase> Encode<'q, DB> for Bind {
+    fn encode_by_ref(&self, buf: &mut DB::ArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        DB::encode_bind(self, buf)
+    }
+
+    fn produces(&self) -> Option<DB::TypeInfo> {
+        Some(DB::bind_type(self))
+    }
+}
+
+impl<DB: SqlxDatabase> Type<DB> for Bind {
+    // The real type comes from `Encode::produces`
+    fn type_info() -> DB::TypeInfo {
+        DB::bind_type(&Bind::String(String::new()))
+    }
+
+    fn compatible(_: &DB::TypeInfo) -> bool {
+        true
+    }
+}
+
+// MySQL and SQLite take dates and times as text. `U64` is sent as a signed 64 bit integer and
+// fails if it doesn't fit.
+#[cfg(any(feature = "sqlx-mysql", feature = "sqlx-sqlite"))]
+macro_rules! impl_sqlx_database_with_text_dates {
+    ($db:ty, $dialect:expr) => {
+        impl SqlxDatabase for $db {
+            fn dialect() -> &'static dyn Dialect {
+                &$dialect
+            }
+
+            fn encode_bind(
+                bind: &Bind,
+                buf: &mut Self::ArgumentBuffer,
+            ) -> Result<IsNull, BoxDynError> {
+                match bind {
+                    Bind::String(s) | Bind::Numeric(s) | Bind::Json(s) | Bind::Jsonb(s) => {
+                        Encode::<$db>::encode_by_ref(s, buf)
+                    }
+                    Bind::I32(n) => Encode::<$db>::encode_by_ref(n, buf),
+                    Bind::U64(n) => Encode::<$db>::encode(i64::try_from(*n)?, buf),
+                    Bind::I16(n) => Encode::<$db>::encode_by_ref(n, buf),
+                    Bind::I64(n) => Encode::<$db>::encode_by_ref(n, buf),
+                    Bind::Bool(b) => Encode::<$db>::encode_by_ref(b, buf),
+                    Bind::F32(n) => Encode::<$db>::encode_by_ref(n, buf),
+                    Bind::F64(n) => Encode::<$db>::encode_by_ref(n, buf),
+                    Bind::Bytes(bytes) => Encode::<$db>::encode_by_ref(bytes, buf),
+                    Bind::Uuid(bytes) => Encode::<$db>::encode(bytes.to_vec(), buf),
+                    Bind::Custom { bytes, .. } => match std::str::from_utf8(bytes) {
+                        Ok(text) => Encode::<$db>::encode(text.to_string(), buf),
+                        Err(_) => Encode::<$db>::encode_by_ref(bytes, buf),
+                    },
+                    Bind::Date(days) => Encode::<$db>::encode(format_date(*days), buf),
+                    Bind::Time(micros) => Encode::<$db>::encode(format_time(*micros), buf),
+                    Bind::Timestamp(micros) | Bind::Timestamptz(micros) => {
+                        Encode::<$db>::encode(format_timestamp(*micros), buf)
+                    }
+                    Bind::Array { .. } => Err(format!(
+                        "{} doesn't support arrays",
+                        <Self as SqlxDatabase>::dialect().name()
+                    )
+                    .into()),
+                    Bind::Interval { .. } => Err(format!(
+                        "{} doesn't support intervals",
+                        <Self as SqlxDatabase>::dialect().name()
+                    )
+                    .into()),
+                }
+            }
+
+            fn bind_type(bind: &Bind) -> Self::TypeInfo {
+                match bind {
+                    Bind::String(_)
+                    | Bind::Numeric(_)
+                    | Bind::Json(_)
+                    | Bind::Jsonb(_)
+                    | Bind::Date(_)
+                    | Bind::Time(_)
+                    | Bind::Timestamp(_)
+                    | Bind::Timestamptz(_)
+                    | Bind::Array { .. }
+                    | Bind::Interval { .. } => <String as Type<$db>>::type_info(),
+                    Bind::I32(_) => <i32 as Type<$db>>::type_info(),
+                    Bind::U64(_) | Bind::I64(_) => <i64 as Type<$db>>::type_info(),
+                    Bind::I16(_) => <i16 as Type<$db>>::type_info(),
+                    Bind::Bool(_) => <bool as Type<$db>>::type_info(),
+                    Bind::F32(_) => <f32 as Type<$db>>::type_info(),
+                    Bind::F64(_) => <f64 as Type<$db>>::type_info(),
+                    Bind::Bytes(_) | Bind::Uuid(_) => <Vec<u8> as Type<$db>>::type_info(),
+                    Bind::Custom { bytes, .. } => match std::str::from_utf8(bytes) {
+                        Ok(_) => <String as Type<$db>>::type_info(),
+                        Err(_) => <Vec<u8> as Type<$db>>::type_info(),
+                    },
+                }
+            }
+        }
+    };
+}
+
+#[cfg(feature = "sqlx-mysql")]
+impl_sqlx_database_with_text_dates!(sqlx::MySql, crate::MySql);
+
+#[cfg(feature = "sqlx-sqlite")]
+impl_sqlx_database_with_text_dates!(sqlx::Sqlite, crate::Sqlite);
+
+#[cfg(feature = "sqlx-postgres")]
+pub(crate) mod postgres {
+    use super::SqlxDatabase;
+    use crate::pg_encoding::{
+        builtin_oids, encode_array, encode_numeric, PG_EPOCH_DAYS, PG_EPOCH_MICROS,
+    };
+    use crate::{Bind, Dialect};
+    use sqlx::encode::IsNull;
+    use sqlx::error::BoxDynError;
+    use sqlx::postgres::{PgArgumentBuffer, PgBindIterExt, PgTypeInfo};
+    use sqlx::{Encode, Postgres, Type};
+    use std::convert::TryFrom;
+
+    impl SqlxDatabase for Postgres {
+        fn dialect() -> &'static dyn Dialect {
+            &crate::Postgres
+        }
+
+        fn encode_bind(bind: &Bind, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+            match bind {
+                Bind::String(s) | Bind::Json(s) => Encode::<Postgres>::encode_by_ref(s, buf),
+                Bind::I32(n) => Encode::<Postgres>::encode_by_ref(n, buf),
+                Bind::U64(n) => Encode::<Postgres>::encode(i64::try_from(*n)?, buf),
+                Bind::I16(n) => Encode::<Postgres>::encode_by_ref(n, buf),
+                Bind::I64(n) => Encode::<Postgres>::encode_by_ref(n, buf),
+                Bind::Bool(b) => Encode::<Postgres>::encode_by_ref(b, buf),
+                Bind::F32(n) => Encode::<Postgres>::encode_by_ref(n, buf),
+                Bind::F64(n) => Encode::<Postgres>::encode_by_ref(n, buf),
+                Bind::Bytes(bytes) => Encode::<Postgres>::encode_by_ref(bytes, buf),
+                Bind::Numeric(s) => {
+                    encode_numeric(s, buf)?;
+                    Ok(IsNull::No)
+                }
+                Bind::Date(days) => {
+                    let days = days.checked_sub(PG_EPOCH_DAYS).ok_or("date out of range")?;
+                    Encode::<Postgres>::encode(days, buf)
+                }
+                Bind::Time(micros) => Encode::<Postgres>::encode_by_ref(micros, buf),
+                Bind::Timestamp(micros) | Bind::Timestamptz(micros) => {
+                    let micros = micros
+                        .checked_sub(PG_EPOCH_MICROS)
+                        .ok_or("timestamp out of range")?;
+                    Encode::<Postgres>::encode(micros, buf)
+                }
+                Bind::Uuid(bytes) => {
+                    buf.extend_from_slice(bytes);
+                    Ok(IsNull::No)
+                }
+                Bind::Jsonb(s) => {
+                    // The jsonb format version
+                    buf.push(1);
+                    buf.extend_from_slice(s.as_bytes());
+                    Ok(IsNull::No)
+                }
+                Bind::Interval {
+                    months,
+                    days,
+                    micros,
+                } => {
+                    buf.extend_from_slice(&micros.to_be_bytes());
+                    buf.extend_from_slice(&days.to_be_bytes());
+                    buf.extend_from_slice(&months.to_be_bytes());
+                    Ok(IsNull::No)
+                }
+                Bind::Custom { bytes, .. } => {
+                    buf.extend_from_slice(bytes);
+                    Ok(IsNull::No)
+                }
+                // `bind_iter` takes the element type from the first element, so only empty arrays
+                // need a known OID
+                Bind::Array {
+                    element_type,
+                    values,
+                } if values.is_empty() => {
+                    let (oid, _) = builtin_oids(element_type).ok_or_else(|| {
+                        format!("empty arrays of `{}` can't be sent with sqlx", element_type)
+                    })?;
+                    encode_array::<Bind>(oid, &[], buf, |_, _| Ok(()))?;
+                    Ok(IsNull::No)
+                }
+                Bind::Array { values, .. } => values.iter().bind_iter().encode_by_ref(buf),
+            }
+        }
+
+        fn bind_type(bind: &Bind) -> PgTypeInfo {
+            match bind {
+                Bind::String(_) => <String as Type<Postgres>>::type_info(),
+                Bind::I32(_) => <i32 as Type<Postgres>>::type_info(),
+                Bind::U64(_) | Bind::I64(_) => <i64 as Type<Postgres>>::type_info(),
+                Bind::I16(_) => <i16 as Type<Postgres>>::type_info(),
+                Bind::Bool(_) => <bool as Type<Postgres>>::type_info(),
+                Bind::F32(_) => <f32 as Type<Postgres>>::type_info(),
+                Bind::F64(_) => <f64 as Type<Postgres>>::type_info(),
+                Bind::Bytes(_) => <Vec<u8> as Type<Postgres>>::type_info(),
+                Bind::Numeric(_) => PgTypeInfo::with_name("numeric"),
+                Bind::Date(_) => PgTypeInfo::with_name("date"),
+                Bind::Time(_) => PgTypeInfo::with_name("time"),
+                Bind::Timestamp(_) => PgTypeInfo::with_name("timestamp"),
+                Bind::Timestamptz(_) => PgTypeInfo::with_name("timestamptz"),
+                Bind::Uuid(_) => PgTypeInfo::with_name("uuid"),
+                Bind::Json(_) => PgTypeInfo::with_name("json"),
+                Bind::Jsonb(_) => PgTypeInfo::with_name("jsonb"),
+                Bind::Interval { .. } => PgTypeInfo::with_name("interval"),
+                Bind::Custom { type_name, .. } => PgTypeInfo::with_name(type_name),
+                Bind::Array { element_type, .. } => PgTypeInfo::array_of(element_type),
+            }
+        }
+    }
+}
+
+fn into_query<DB: SqlxDatabase>(sql: String, binds: Binds) -> Query<'static, DB, DB::Arguments> {
+    binds.fold(sqlx::query(AssertSqlSafe(sql)), |query, bind| {
+        query.bind(bind)
+    })
+}
+
+fn into_query_as<DB, O>(sql: String, binds: Binds) -> QueryAs<'static, DB, O, DB::Arguments>
+where
+    DB: SqlxDatabase,
+    O: for<'r> FromRow<'r, DB::Row>,
+{
+    binds.fold(sqlx::query_as(AssertSqlSafe(sql)), |query, bind| {
+        query.bind(bind)
+    })
+}
+
+impl<T> QueryWithSelect<T> {
+    /// Render the query for `DB` and bind every parameter.
+    pub fn into_sqlx<DB>(self) -> Result<Query<'static, DB, DB::Arguments>, Error>
+    where
+        DB: SqlxDatabase,
+    {
+        let (sql, binds) = self.to_sql_with(DB::dialect())?;
+        Ok(into_query(sql, binds))
+    }
+
+    /// Like `into_sqlx` but maps the rows to `O`.
+    pub fn into_sqlx_as<DB, O>(self) -> Result<QueryAs<'static, DB, O, DB::Arguments>, Error>
+    where
+        DB: SqlxDatabase,
+        O: for<'r> FromRow<'r, DB::Row>,
+    {
+        let (sql, binds) = self.to_sql_with(DB::dialect())?;
+        Ok(into_query_as(sql, binds))
+    }
+}
+
+impl<T> SetOperation<T> {
+    /// Render the query for `DB` and bind every parameter.
+    pub fn into_sqlx<DB>(self) -> Result<Query<'static, DB, DB::Arguments>, Error>
+    where
+        DB: SqlxDatabase,
+    {
+        let (sql, binds) = self.to_sql_with(DB::dialect())?;
+        Ok(into_query(sql, binds))
+    }
+
+    /// Like `into_sqlx` but maps the rows to `O`.
+    pub fn into_sqlx_as<DB, O>(self) -> Result<QueryAs<'static, DB, O, DB::Arguments>, Error>
+    where
+        DB: SqlxDatabase,
+        O: for<'r> FromRow<'r, DB::Row>,
+    {
+        let (sql, binds) = self.to_sql_with(DB::dialect())?;
+        Ok(into_query_as(sql, binds))
+    }
+}
@@ -0,0 +1,200 @@
+use crate::binds::Bind;
+use crate::sql_types::{self, AtTimeZone, Date, Double, IntervalArithmetic, Timestamptz};
+use crate::{BinOp, Expr, IntoExpr, TypedExpr};
+use std::fmt;
+
+/// A Postgres `interval`, such as `Interval::days(7)`.
+///
+/// Months and days are kept apart from the rest since their length varies, so one month added
+/// to January 31st gives the last day of February.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub struct Interval {
+    pub months: i32,
+    pub days: i32,
+    pub micros: i64,
+}
+
+impl Interval {
+    pub fn new(months: i32, days: i32, micros: i64) -> Self {
+        Self {
+            months,
+            days,
+            micros,
+        }
+    }
+
+    pub fn years(years: i32) -> Self {
+        Self::months(years * 12)
+    }
+
+    pub fn months(months: i32) -> Self {
+        Self::new(months, 0, 0)
+    }
+
+    pub fn weeks(weeks: i32) -> Self {
+        Self::days(weeks * 7)
+    }
+
+    pub fn days(days: i32) -> Self {
+        Self::new(0, days, 0)
+    }
+
+    pub fn hours(hours: i64) -> Self {
+        Self::minutes(hours * 60)
+    }
+
+    pub fn minutes(minutes: i64) -> Self {
+        Self::seconds(minutes * 60)
+    }
+
+    pub fn seconds(seconds: i64) -> Self {
+        Self::new(0, 0, seconds * 1_000_000)
+    }
+}
+
+impl From<Interval> for Bind {
+    fn from(interval: Interval) -> Self {
+        Bind::Interval {
+            months: interval.months,
+            days: interval.days,
+            micros: interval.micros,
+        }
+    }
+}
+
+impl IntoExpr for Interval {
+    type SqlType = sql_types::Interval;
+
+    fn into_expr(self) -> Expr {
+        Expr::Bind(self.into())
+    }
+}
+
+/// Arithmetic and time zone conversion of dates, timestamps and intervals.
+pub trait DateTimeExprDsl: IntoExpr + Sized {
+    /// `self + interval`
+    fn add<I>(self, interval: I) -> TypedExpr<<Self::SqlType as IntervalArithmetic>::Output>
+    where
+        Self::SqlType: IntervalArithmetic,
+        I: IntoExpr<SqlType = sql_types::Interval>,
+    {
+        TypedExpr::new(Expr::BinOp {
+            lhs: Box::new(self.into_expr()),
+            op: BinOp::Add,
+            rhs: Box::new(interval.into_expr()),
+        })
+    }
+
+    /// `self - interval`
+    fn sub<I>(self, interval: I) -> TypedExpr<<Self::SqlType as IntervalArithmetic>::Output>
+    where
+        Self::SqlType: IntervalArithmetic,
+        I: IntoExpr<SqlType = sql_types::Interval>,
+    {
+        TypedExpr::new(Expr::BinOp {
+            lhs: Box::new(self.into_expr()),
+            op: BinOp::Sub,
+            rhs: Box::new(interval.into_expr()),
+        })
+    }
+
+    /// `self AT TIME ZONE zone`, which converts between `timestamp` and `timestamptz`.
+    fn at_time_zone(self, zone: &str) -> TypedExpr<<Self::SqlType as AtTimeZone>::Output>
+    where
+        Self::SqlType: AtTimeZone,
+    {
+        TypedExpr::new(Expr::BinOp {
+            lhs: Box::new(self.into_expr()),
+            op: BinOp::AtTimeZone,
+            rhs: Box::new(zone.into_expr()),
+        })
+    }
+}
+
+impl<T: IntoExpr> DateTimeExprDsl for T {}
+
+/// `CURRENT_TIMESTAMP`, which is the same as `now()` in Postgres and is understood by MySQL and
+/// SQLite as well.
+pub fn now() -> TypedExpr<Timestamptz> {
+    TypedExpr::new(Expr::Raw("CURRENT_TIMESTAMP".to_string()))
+}
+
+/// `CURRENT_DATE`
+pub fn current_date() -> TypedExpr<Date> {
+    TypedExpr::new(Expr::Raw("CURRENT_DATE".to_string()))
+}
+
+/// A field of a date, time or interval, as used by `date_trunc` and `extract`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum DatePart {
+    Microseconds,
+    Milliseconds,
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year,
+    Decade,
+    Century,
+    Millennium,
+    /// Only for `extract`. The day of the week, Sunday being 0.
+    Dow,
+    /// Only for `extract`. The day of the year.
+    Doy,
+    /// Only for `extract`. Seconds since 1970-01-01 00:00:00 UTC, or the length of an interval
+    /// in seconds.
+    Epoch,
+}
+
+impl fmt::Display for DatePart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            DatePart::Microseconds => "microseconds",
+            DatePart::Milliseconds => "milliseconds",
+            DatePart::Second => "second",
+            DatePart::Minute => "minute",
+            DatePart::Hour => "hour",
+            DatePart::Day => "day",
+            DatePart::Week => "week",
+            DatePart::Month => "month",
+            DatePart::Quarter => "quarter",
+            DatePart::Year => "year",
+            DatePart::Decade => "decade",
+            DatePart::Century => "century",
+            DatePart::Millennium => "millennium",
+            DatePart::Dow => "dow",
+            DatePart::Doy => "doy",
+            DatePart::Epoch => "epoch",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// `date_trunc('part', expr)`. The part is written as a literal rather than bound so the
+/// expression can match an expression index.
+pub fn date_trunc<T>(part: DatePart, expr: T) -> TypedExpr<T::SqlType>
+where
+    T: IntoExpr,
+    T::SqlType: IntervalArithmetic<Output = T::SqlType>,
+{
+    TypedExpr::new(Expr::Function {
+        name: "date_trunc",
+        args: vec![Expr::Raw(format!("'{}'", part)), expr.into_expr()],
+    })
+}
+
+/// `EXTRACT(part FROM expr)`. Postgres returns `numeric` since version 14, which compares with
+/// `f64` binds as well.
+pub fn extract<T>(part: DatePart, expr: T) -> TypedExpr<Double>
+where
+    T: IntoExpr,
+    T::SqlType: IntervalArithmetic,
+{
+    TypedExpr::new(Expr::Extract {
+        part,
+        expr: Box::new(expr.into_expr()),
+    })
+}
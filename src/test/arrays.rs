@@ -0,0 +1,193 @@
+use crate::*;
+
+table! {
+    users {
+        id -> Integer,
+        name -> Text,
+        tags -> Array<Text>,
+        scores -> Array<Integer>,
+    }
+}
+
+#[test]
+fn array_operators() {
+    let (sql, mut binds) = users::table
+        .filter(users::tags.contains(vec!["admin"]))
+        .filter(users::tags.is_contained_by(vec!["admin", "staff"]))
+        .filter(users::tags.overlaps(users::tags))
+        .select(users::id)
+        .to_sql();
+
+    assert_eq!(
+        sql,
+        r#"SELECT "users"."id" FROM "users" WHERE "users"."tags" @> $1 AND "users"."tags" <@ $2 AND "users"."tags" && "users"."tags""#
+    );
+    assert_eq!(
+        binds.next(),
+        Some(Bind::Array {
+            element_type: "text",
+            values: vec![Bind::String("admin".to_string())],
+        })
+    );
+    assert_eq!(
+        binds.next(),
+        Some(Bind::Array {
+            element_type: "text",
+            values: vec![
+                Bind::String("admin".to_string()),
+                Bind::String("staff".to_string()),
+            ],
+        })
+    );
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn eq_any() {
+    let (sql, mut binds) = users::table
+        .filter(users::id.eq_any(vec![1, 2]))
+        .filter(users::name.eq_any(users::tags))
+        .select(users::id)
+        .to_sql();
+
+    assert_eq!(
+        sql,
+        r#"SELECT "users"."id" FROM "users" WHERE "users"."id" = ANY($1) AND "users"."name" = ANY("users"."tags")"#
+    );
+    assert_eq!(
+        binds.next(),
+        Some(Bind::Array {
+            element_type: "int4",
+            values: vec![Bind::I32(1), Bind::I32(2)],
+        })
+    );
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn empty_arrays_keep_their_element_type() {
+    let (_, mut binds) = users::table
+        .filter(users::scores.overlaps(Vec::<i32>::new()))
+        .select(users::id)
+        .to_sql();
+
+    assert_eq!(
+        binds.next(),
+        Some(Bind::Array {
+            element_type: "int4",
+            values: vec![],
+        })
+    );
+}
+
+#[test]
+fn array_length_and_index() {
+    let (sql, mut binds) = users::table
+        .filter(users::tags.array_length().gt(2))
+        .filter(users::tags.index(1).eq("admin"))
+        .filter(users::scores.index(2).is_null())
+        .select(users::id)
+        .to_sql();
+
+    assert_eq!(
+        sql,
+        r#"SELECT "users"."id" FROM "users" WHERE array_length("users"."tags", 1) > $1 AND "users"."tags"[1] = $2 AND "users"."scores"[2] IS NULL"#
+    );
+    assert_eq!(binds.next(), Some(Bind::I32(2)));
+    assert_eq!(binds.next(), Some(Bind::String("admin".to_string())));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn arrays_of_values_and_enums() {
+    #[derive(SqlEnum)]
+    #[sql_enum(type_name = "user_role")]
+    pub enum Role {
+        Admin,
+    }
+
+    let (_, binds) = users::table
+        .filter(Value::date(1).eq_any(vec![Value::date(1), Value::date(2)]))
+        .filter(Role::Admin.eq_any(vec![Role::Admin]))
+        .select(users::id)
+        .to_sql();
+
+    let binds = binds.collect::<Vec<_>>();
+    assert_eq!(
+        binds[1],
+        Bind::Array {
+            element_type: "date",
+            values: vec![Bind::Date(1), Bind::Date(2)],
+        }
+    );
+    assert_eq!(
+        binds[3],
+        Bind::Array {
+            element_type: "user_role",
+            values: vec![Bind::Custom {
+                type_name: "user_role",
+                bytes: b"admin".to_vec(),
+            }],
+        }
+    );
+}
+
+#[test]
+fn arrays_are_postgres_only() {
+    let err = users::table
+        .filter(users::id.eq_any(vec![1]))
+        .select(users::id)
+        .to_sql_with(&MySql)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Error::Unsupported {
+            dialect: "MySQL",
+            feature: Feature::Arrays,
+        }
+    );
+
+    let err = users::table
+        .filter(users::tags.index(1).eq("admin"))
+        .select(users::id)
+        .to_sql_with(&Sqlite)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Error::Unsupported {
+            dialect: "SQLite",
+            feature: Feature::Arrays,
+        }
+    );
+
+    for filter in [
+        users::tags.contains(users::tags),
+        users::tags.is_contained_by(users::tags),
+        users::tags.overlaps(users::tags),
+    ] {
+        let err = users::table
+            .filter(filter)
+            .select(users::id)
+            .to_sql_with(&Sqlite)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            Error::Unsupported {
+                dialect: "SQLite",
+                feature: Feature::Arrays,
+            }
+        );
+    }
+
+    let err = users::table
+        .select(users::tags.array_length())
+        .to_sql_with(&MySql)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Error::Unsupported {
+            dialect: "MySQL",
+            feature: Feature::Arrays,
+        }
+    );
+}
@@ -0,0 +1,128 @@
+use crate::*;
+
+table! {
+    users {
+        id -> Integer,
+        name -> Text,
+        country_id -> Integer,
+    }
+}
+
+#[test]
+fn count_replaces_select_list() {
+    let (sql, mut binds) = users::table
+        .filter(users::name.eq("Bob"))
+        .order_by(users::id)
+        .for_update()
+        .select(users::star)
+        .count_query()
+        .to_sql();
+
+    assert_eq!(
+        sql,
+        r#"SELECT count(*) FROM "users" WHERE "users"."name" = $1"#
+    );
+    assert_eq!(binds.next(), Some(Bind::String("Bob".to_string())));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn count_grouped_query() {
+    let (sql, _) = users::table
+        .group_by(users::country_id)
+        .order_by(users::country_id)
+        .select((users::country_id, Select::raw("count(*)")))
+        .count_query()
+        .to_sql();
+
+    assert_eq!(
+        sql,
+        r#"SELECT count(*) FROM (SELECT "users"."country_id", count(*) FROM "users" GROUP BY "users"."country_id") "sub""#
+    );
+}
+
+#[test]
+fn count_distinct_on_query() {
+    let (sql, _) = users::table
+        .distinct_on(users::country_id)
+        .order_by((users::country_id, users::id))
+        .select(users::star)
+        .count_query()
+        .to_sql();
+
+    assert_eq!(
+        sql,
+        r#"SELECT count(*) FROM (SELECT DISTINCT ON ("users"."country_id") "users".* FROM "users") "sub""#
+    );
+}
+
+#[test]
+fn count_limited_query() {
+    let (sql, mut binds) = users::table
+        .filter(users::country_id.eq(1))
+        .order_by(users::name)
+        .limit(10)
+        .offset(5)
+        .select(users::id)
+        .count_query()
+        .to_sql();
+
+    assert_eq!(
+        sql,
+        r#"SELECT count(*) FROM (SELECT "users"."id" FROM "users" WHERE "users"."country_id" = $1 ORDER BY "users"."name" LIMIT $2 OFFSET $3) "sub""#
+    );
+    assert_eq!(binds.next(), Some(Bind::I32(1)));
+    assert_eq!(binds.next(), Some(Bind::I32(10)));
+    assert_eq!(binds.next(), Some(Bind::I32(5)));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn count_explained_query() {
+    let (sql, _) = users::table
+        .group_by(users::country_id)
+        .explain()
+        .select(users::country_id)
+        .count_query()
+        .to_sql();
+
+    assert_eq!(
+        sql,
+        r#"EXPLAIN SELECT count(*) FROM (SELECT "users"."country_id" FROM "users" GROUP BY "users"."country_id") "sub""#
+    );
+}
+
+#[test]
+fn count_aggregated_query() {
+    let (sql, _) = users::table
+        .filter(users::name.eq("Bob"))
+        .select(count(star()))
+        .count_query()
+        .to_sql();
+    assert_eq!(
+        sql,
+        r#"SELECT count(*) FROM (SELECT count(*) FROM "users" WHERE "users"."name" = $1) "sub""#
+    );
+
+    let (sql, _) = users::table
+        .select(json_agg(users::name))
+        .count_query()
+        .to_sql();
+    assert_eq!(
+        sql,
+        r#"SELECT count(*) FROM (SELECT json_agg("users"."name") FROM "users") "sub""#
+    );
+}
+
+#[test]
+fn count_query_with_having_only() {
+    let (sql, _) = users::table
+        .having(Filter::raw("count(*) > 1"))
+        .select(users::id)
+        .count_query()
+        .to_sql();
+    assert_eq!(
+        sql,
+        r#"SELECT count(*) FROM (SELECT "users"."id" FROM "users" HAVING count(*) > 1) "sub""#
+    );
+}
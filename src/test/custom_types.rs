@@ -0,0 +1,177 @@
+use crate::*;
+
+#[derive(Debug, Copy, Clone, SqlEnum)]
+#[sql_enum(type_name = "user_status")]
+pub enum Status {
+    Active,
+    Banned,
+    OnHold,
+    #[sql_enum(rename = "deleted_by_admin")]
+    Deleted,
+}
+
+#[derive(Debug, Copy, Clone, SqlEnum)]
+#[sql_enum(text)]
+pub enum Role {
+    Admin,
+    Member,
+}
+
+table! {
+    users {
+        id -> Integer,
+        status -> super::Status,
+        role -> Text,
+        previous_status -> Nullable<super::Status>,
+    }
+}
+
+#[test]
+fn enum_binds_carry_the_type_name() {
+    let (sql, mut binds) = users::table
+        .filter(users::status.eq(Status::Active))
+        .filter(users::previous_status.ne(Status::Banned))
+        .select(users::id)
+        .to_sql();
+
+    assert_eq!(
+        sql,
+        r#"SELECT "users"."id" FROM "users" WHERE "users"."status" = CAST($1 AS user_status) AND "users"."previous_status" != CAST($2 AS user_status)"#
+    );
+    assert_eq!(
+        binds.next(),
+        Some(Bind::Custom {
+            type_name: "user_status",
+            bytes: b"active".to_vec(),
+        })
+    );
+    assert_eq!(
+        binds.next(),
+        Some(Bind::Custom {
+            type_name: "user_status",
+            bytes: b"banned".to_vec(),
+        })
+    );
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn custom_binds_are_only_cast_on_postgres() {
+    let (sql, _) = users::table
+        .filter(users::status.eq(Status::Active))
+        .select(users::id)
+        .to_sql_with(&Sqlite)
+        .unwrap();
+
+    assert_eq!(
+        sql,
+        r#"SELECT "users"."id" FROM "users" WHERE "users"."status" = ?"#
+    );
+}
+
+#[test]
+fn text_enums_bind_as_strings() {
+    let (sql, mut binds) = users::table
+        .filter(users::role.eq(Role::Admin))
+        .filter(users::role.ne("guest"))
+        .select(users::id)
+        .to_sql();
+
+    assert_eq!(
+        sql,
+        r#"SELECT "users"."id" FROM "users" WHERE "users"."role" = $1 AND "users"."role" != $2"#
+    );
+    assert_eq!(binds.next(), Some(Bind::String("admin".to_string())));
+    assert_eq!(binds.next(), Some(Bind::String("guest".to_string())));
+    assert_eq!(binds.next(), None);
+}
+
+pub struct Point {
+    x: i16,
+    y: i16,
+}
+
+impl CustomType for Point {
+    type SqlType = Point;
+
+    fn to_bind(&self) -> Bind {
+        let mut bytes = self.x.to_be_bytes().to_vec();
+        bytes.extend_from_slice(&self.y.to_be_bytes());
+        Bind::Custom {
+            type_name: "point2d",
+            bytes,
+        }
+    }
+}
+
+table! {
+    shapes {
+        id -> Integer,
+        origin -> super::Point,
+    }
+}
+
+#[test]
+fn custom_types() {
+    let (_, mut binds) = shapes::table
+        .filter(shapes::origin.eq(Point { x: 1, y: -1 }))
+        .select(shapes::id)
+        .to_sql();
+
+    assert_eq!(
+        binds.next(),
+        Some(Bind::Custom {
+            type_name: "point2d",
+            bytes: vec![0, 1, 255, 255],
+        })
+    );
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn enum_labels() {
+    #[derive(SqlEnum)]
+    #[sql_enum(text)]
+    enum ErrorKind {
+        HTTPError,
+        IOError,
+        Http2Error,
+        Timeout,
+    }
+
+    assert_eq!(Status::Banned.as_str(), "banned");
+    assert_eq!(Status::OnHold.as_str(), "on_hold");
+    assert_eq!(Status::Deleted.as_str(), "deleted_by_admin");
+    assert_eq!(Role::Member.as_str(), "member");
+
+    // Runs of capitals are one word
+    assert_eq!(ErrorKind::HTTPError.as_str(), "http_error");
+    assert_eq!(ErrorKind::IOError.as_str(), "io_error");
+    assert_eq!(ErrorKind::Http2Error.as_str(), "http2_error");
+    assert_eq!(ErrorKind::Timeout.as_str(), "timeout");
+}
+
+#[test]
+fn enums_are_array_elements() {
+    let (_, mut binds) = users::table
+        .filter(users::status.eq_any(vec![Status::Active, Status::OnHold]))
+        .select(users::id)
+        .to_sql();
+
+    assert_eq!(
+        binds.next(),
+        Some(Bind::Array {
+            element_type: "user_status",
+            values: vec![
+                Bind::Custom {
+                    type_name: "user_status",
+                    bytes: b"active".to_vec(),
+                },
+                Bind::Custom {
+                    type_name: "user_status",
+                    bytes: b"on_hold".to_vec(),
+                },
+            ],
+        })
+    );
+}
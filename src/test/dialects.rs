@@ -0,0 +1,487 @@
+use crate::*;
+use std::fmt::{self, Write};
+
+table! {
+    users {
+        id -> Integer,
+        name -> Text,
+        country_id -> Integer,
+    }
+}
+
+table! {
+    countries {
+        id -> Integer,
+        name -> Text,
+    }
+}
+
+struct MsSql;
+
+impl Dialect for MsSql {
+    fn name(&self) -> &'static str {
+        "SQL Server"
+    }
+
+    fn write_placeholder(&self, f: &mut dyn Write, index: usize) -> fmt::Result {
+        write!(f, "@p{}", index)
+    }
+
+    fn write_identifier(&self, f: &mut dyn Write, ident: &str) -> fmt::Result {
+        write!(f, "[{}]", ident)
+    }
+}
+
+struct Oracle;
+
+impl Dialect for Oracle {
+    fn name(&self) -> &'static str {
+        "Oracle"
+    }
+
+    fn write_placeholder(&self, f: &mut dyn Write, index: usize) -> fmt::Result {
+        write!(f, ":p{}", index)
+    }
+}
+
+fn query() -> QueryWithSelect<users::table> {
+    users::table
+        .inner_join(countries::table.on(countries::id.eq(users::country_id)))
+        .filter(users::id.eq(1))
+        .filter(countries::name.eq("Denmark"))
+        .limit(10)
+        .select((users::star, countries::name))
+}
+
+#[test]
+fn postgres() {
+    let (sql, mut binds) = query().to_sql_with(&Postgres).unwrap();
+
+    assert_eq!(
+        sql,
+        r#"SELECT "users".*, "countries"."name" FROM "users" INNER JOIN "countries" ON "countries"."id" = "users"."country_id" WHERE "users"."id" = $1 AND "countries"."name" = $2 LIMIT $3"#
+    );
+    assert_eq!(binds.next(), Some(Bind::I32(1)));
+    assert_eq!(binds.next(), Some(Bind::String("Denmark".to_string())));
+    assert_eq!(binds.next(), Some(Bind::I32(10)));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn mysql() {
+    let (sql, mut binds) = query().to_sql_with(&MySql).unwrap();
+
+    assert_eq!(
+        sql,
+        r#"SELECT `users`.*, `countries`.`name` FROM `users` INNER JOIN `countries` ON `countries`.`id` = `users`.`country_id` WHERE `users`.`id` = ? AND `countries`.`name` = ? LIMIT ?"#
+    );
+    assert_eq!(binds.next(), Some(Bind::I32(1)));
+    assert_eq!(binds.next(), Some(Bind::String("Denmark".to_string())));
+    assert_eq!(binds.next(), Some(Bind::I32(10)));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn sqlite() {
+    let (sql, _) = query().to_sql_with(&Sqlite).unwrap();
+
+    assert_eq!(
+        sql,
+        r#"SELECT "users".*, "countries"."name" FROM "users" INNER JOIN "countries" ON "countries"."id" = "users"."country_id" WHERE "users"."id" = ? AND "countries"."name" = ? LIMIT ?"#
+    );
+}
+
+#[test]
+fn custom_dialect() {
+    let sub_query = users::table.filter(users::id.gt(1)).select(users::id);
+
+    let (sql, _) = from(sub_query.alias("big ids"))
+        .with(countries::table.select(countries::id).alias("c"))
+        .filter(users::name.eq("Bob"))
+        .select(users::id)
+        .to_sql_with(&MsSql)
+        .unwrap();
+
+    assert_eq!(
+        sql,
+        r#"WITH [c] AS (SELECT [countries].[id] FROM [countries]) SELECT [users].[id] FROM (SELECT [users].[id] FROM [users] WHERE [users].[id] > @p1) [big ids] WHERE [users].[name] = @p2"#
+    );
+}
+
+#[test]
+fn named_placeholders() {
+    let (sql, mut binds) = query().to_sql_with(&Oracle).unwrap();
+
+    assert_eq!(
+        sql,
+        r#"SELECT "users".*, "countries"."name" FROM "users" INNER JOIN "countries" ON "countries"."id" = "users"."country_id" WHERE "users"."id" = :p1 AND "countries"."name" = :p2 LIMIT :p3"#
+    );
+    assert_eq!(binds.next(), Some(Bind::I32(1)));
+}
+
+#[test]
+fn no_wait() {
+    let query = || users::table.for_update().no_wait().select(users::id);
+
+    let (sql, _) = query().to_sql_with(&Postgres).unwrap();
+    assert_eq!(sql, r#"SELECT "users"."id" FROM "users" FOR UPDATE NOWAIT"#);
+
+    let (sql, _) = query().to_sql_with(&MySql).unwrap();
+    assert_eq!(sql, r#"SELECT `users`.`id` FROM `users` FOR UPDATE NOWAIT"#);
+}
+
+#[test]
+fn quoting_escapes_identifiers() {
+    let sub_query = users::table.select(users::id);
+
+    let (sql, _) = from(sub_query.clone().alias(r#"a "quoted" name"#))
+        .select(star())
+        .to_sql_with(&Postgres)
+        .unwrap();
+    assert_eq!(
+        sql,
+        r#"SELECT * FROM (SELECT "users"."id" FROM "users") "a ""quoted"" name""#
+    );
+
+    let (sql, _) = from(sub_query.alias("a `ticked` name"))
+        .select(star())
+        .to_sql_with(&MySql)
+        .unwrap();
+    assert_eq!(
+        sql,
+        "SELECT * FROM (SELECT `users`.`id` FROM `users`) `a ``ticked`` name`"
+    );
+}
+
+#[test]
+fn unsupported_features() {
+    let result = users::table
+        .for_update()
+        .select(users::star)
+        .to_sql_with(&Sqlite);
+    assert_eq!(
+        result.unwrap_err(),
+        Error::Unsupported {
+            dialect: "SQLite",
+            feature: Feature::ForUpdate,
+        }
+    );
+
+    let result = users::table
+        .select(users::id)
+        .intersect_all(countries::table.select(countries::id))
+        .to_sql_with(&Sqlite);
+    assert_eq!(
+        result.unwrap_err(),
+        Error::Unsupported {
+            dialect: "SQLite",
+            feature: Feature::IntersectAll,
+        }
+    );
+}
+
+#[test]
+fn features_are_only_emulated_when_the_dialect_opts_in() {
+    struct NoDistinctOn;
+
+    impl Dialect for NoDistinctOn {
+        fn name(&self) -> &'static str {
+            "NoDistinctOn"
+        }
+
+        fn supports(&self, feature: Feature) -> bool {
+            feature != Feature::DistinctOn
+        }
+    }
+
+    let result = users::table
+        .distinct_on(users::id)
+        .select(users::star)
+        .to_sql_with(&NoDistinctOn);
+    assert_eq!(
+        result.unwrap_err(),
+        Error::Unsupported {
+            dialect: "NoDistinctOn",
+            feature: Feature::DistinctOn,
+        }
+    );
+}
+
+#[test]
+fn emulated_nulls_position() {
+    let (sql, _) = users::table
+        .order_by((users::name.desc().nulls_first(), users::id.nulls_last()))
+        .select(users::id)
+        .to_sql_with(&MySql)
+        .unwrap();
+    assert_eq!(
+        sql,
+        "SELECT `users`.`id` FROM `users` ORDER BY `users`.`name` IS NULL DESC, `users`.`name` DESC, `users`.`id` IS NULL, `users`.`id`"
+    );
+
+    let (sql, _) = users::table
+        .order_by(users::name.desc().nulls_first())
+        .select(users::id)
+        .to_sql_with(&Sqlite)
+        .unwrap();
+    assert_eq!(
+        sql,
+        r#"SELECT "users"."id" FROM "users" ORDER BY "users"."name" DESC NULLS FIRST"#
+    );
+}
+
+#[test]
+fn emulated_offset_without_limit() {
+    let (sql, mut binds) = users::table
+        .offset(10)
+        .select(users::id)
+        .to_sql_with(&Sqlite)
+        .unwrap();
+    assert_eq!(sql, r#"SELECT "users"."id" FROM "users" LIMIT -1 OFFSET ?"#);
+    assert_eq!(binds.next(), Some(Bind::I32(10)));
+    assert_eq!(binds.next(), None);
+
+    let (sql, _) = users::table
+        .offset(10)
+        .select(users::id)
+        .to_sql_with(&MySql)
+        .unwrap();
+    assert_eq!(
+        sql,
+        "SELECT `users`.`id` FROM `users` LIMIT 18446744073709551615 OFFSET ?"
+    );
+
+    let (sql, _) = users::table
+        .offset(10)
+        .select(users::id)
+        .to_sql_with(&Postgres)
+        .unwrap();
+    assert_eq!(sql, r#"SELECT "users"."id" FROM "users" OFFSET $1"#);
+}
+
+#[test]
+fn emulated_distinct_on() {
+    let (sql, mut binds) = users::table
+        .distinct_on(users::country_id)
+        .filter(users::name.eq("Bob"))
+        .order_by((users::country_id, users::id.desc()))
+        .limit(10)
+        .select(users::star)
+        .to_sql_with(&Sqlite)
+        .unwrap();
+    assert_eq!(
+        sql,
+        r#"SELECT * FROM (SELECT "users".*, ROW_NUMBER() OVER (PARTITION BY "users"."country_id" ORDER BY "users"."country_id", "users"."id" DESC) AS "__row_number", "users"."country_id" AS "__order_0", "users"."id" AS "__order_1" FROM "users" WHERE "users"."name" = ?) AS "__distinct_on" WHERE "__row_number" = 1 ORDER BY "__order_0", "__order_1" DESC LIMIT ?"#
+    );
+    assert_eq!(binds.next(), Some(Bind::String("Bob".to_string())));
+    assert_eq!(binds.next(), Some(Bind::I32(10)));
+    assert_eq!(binds.next(), None);
+
+    let (sql, _) = users::table
+        .distinct_on(users::country_id)
+        .order_by(users::country_id.nulls_first())
+        .select(users::id)
+        .to_sql_with(&MySql)
+        .unwrap();
+    assert_eq!(
+        sql,
+        "SELECT * FROM (SELECT `users`.`id`, ROW_NUMBER() OVER (PARTITION BY `users`.`country_id` ORDER BY `users`.`country_id` IS NULL DESC, `users`.`country_id`) AS `__row_number`, `users`.`country_id` AS `__order_0` FROM `users`) AS `__distinct_on` WHERE `__row_number` = 1 ORDER BY `__order_0` IS NULL DESC, `__order_0`"
+    );
+}
+
+#[test]
+fn unsupported_features_in_sub_queries() {
+    let sub_query = users::table.for_key_share().select(users::id);
+
+    let result = from(sub_query.alias("users"))
+        .select(users::id)
+        .to_sql_with(&MySql);
+    assert_eq!(
+        result.unwrap_err(),
+        Error::Unsupported {
+            dialect: "MySQL",
+            feature: Feature::ForKeyShare,
+        }
+    );
+}
+
+#[test]
+fn too_many_binds_depends_on_dialect() {
+    let query = (0..32767).fold(Query::from(users::table), |query, id| {
+        query.inner_join(countries::table.on(countries::id.eq(id)))
+    });
+
+    assert!(query
+        .clone()
+        .select(users::id)
+        .to_sql_with(&Postgres)
+        .is_ok());
+    assert_eq!(
+        query.select(users::id).to_sql_with(&Sqlite).unwrap_err(),
+        Error::TooManyBinds {
+            count: 32767,
+            max: 32766
+        }
+    );
+}
+
+// How far the user id is from `id`, as an expression with a bind
+fn distance_from(id: i32) -> TypedExpr<sql_types::Integer> {
+    TypedExpr::new(Expr::Function {
+        name: "abs",
+        args: vec![Expr::BinOp {
+            lhs: Box::new(users::id.into_expr()),
+            op: BinOp::Sub,
+            rhs: Box::new(Expr::Bind(Bind::I32(id))),
+        }],
+    })
+}
+
+#[test]
+fn emulated_nulls_position_collects_binds_twice() {
+    let (sql, binds) = users::table
+        .filter(users::name.ne("Eve"))
+        .order_by(distance_from(3).desc().nulls_first())
+        .limit(2)
+        .select(users::id)
+        .to_sql_with(&MySql)
+        .unwrap();
+
+    assert_eq!(
+        sql,
+        "SELECT `users`.`id` FROM `users` WHERE `users`.`name` != ? ORDER BY abs(`users`.`id` - ?) IS NULL DESC, abs(`users`.`id` - ?) DESC LIMIT ?"
+    );
+    assert_eq!(
+        binds.collect::<Vec<_>>(),
+        vec![
+            Bind::String("Eve".to_string()),
+            Bind::I32(3),
+            Bind::I32(3),
+            Bind::I32(2)
+        ]
+    );
+}
+
+#[test]
+fn emulated_distinct_on_binds_follow_the_sql() {
+    let (sql, binds) = users::table
+        .filter(users::name.ne("Eve"))
+        .distinct_on(users::country_id)
+        .order_by((users::country_id, distance_from(3).asc().nulls_last()))
+        .select(users::id)
+        .to_sql_with(&MySql)
+        .unwrap();
+
+    assert_eq!(
+        sql,
+        "SELECT * FROM (SELECT `users`.`id`, ROW_NUMBER() OVER (PARTITION BY `users`.`country_id` ORDER BY `users`.`country_id`, abs(`users`.`id` - ?) IS NULL, abs(`users`.`id` - ?) ASC) AS `__row_number`, `users`.`country_id` AS `__order_0`, abs(`users`.`id` - ?) AS `__order_1` FROM `users` WHERE `users`.`name` != ?) AS `__distinct_on` WHERE `__row_number` = 1 ORDER BY `__order_0`, `__order_1` IS NULL, `__order_1` ASC"
+    );
+    assert_eq!(
+        binds.collect::<Vec<_>>(),
+        vec![
+            Bind::I32(3),
+            Bind::I32(3),
+            Bind::I32(3),
+            Bind::String("Eve".to_string())
+        ]
+    );
+}
+
+#[test]
+fn emulated_distinct_on_with_raw_order() {
+    let err = users::table
+        .distinct_on(users::country_id)
+        .order_by((users::country_id, Order::raw("random()")))
+        .select(users::id)
+        .to_sql_with(&Sqlite)
+        .unwrap_err();
+
+    assert_eq!(
+        err,
+        Error::UnsupportedEmulation {
+            dialect: "SQLite",
+            feature: Feature::DistinctOn,
+        }
+    );
+}
+
+#[test]
+fn emulated_nested_set_operations() {
+    let a = users::table.filter(users::id.eq(1)).select(users::id);
+    let b = users::table.filter(users::id.eq(2)).select(users::id);
+    let c = users::table.limit(3).select(users::id);
+
+    // SQLite evaluates set operators left to right, so the rhs is wrapped even though INTERSECT
+    // binds tighter
+    let (sql, _) = a
+        .clone()
+        .union(b.clone().intersect(c.clone()))
+        .to_sql_with(&Sqlite)
+        .unwrap();
+    assert_eq!(
+        sql,
+        r#"SELECT "users"."id" FROM "users" WHERE "users"."id" = ? UNION SELECT * FROM (SELECT "users"."id" FROM "users" WHERE "users"."id" = ? INTERSECT SELECT * FROM (SELECT "users"."id" FROM "users" LIMIT ?) AS "__operand") AS "__operand""#
+    );
+
+    let (sql, _) = a
+        .clone()
+        .union(b.clone())
+        .intersect(c.clone())
+        .to_sql_with(&Sqlite)
+        .unwrap();
+    assert_eq!(
+        sql,
+        r#"SELECT * FROM (SELECT "users"."id" FROM "users" WHERE "users"."id" = ? UNION SELECT "users"."id" FROM "users" WHERE "users"."id" = ?) AS "__operand" INTERSECT SELECT * FROM (SELECT "users"."id" FROM "users" LIMIT ?) AS "__operand""#
+    );
+
+    let (sql, _) = SetOperation::from(c.clone())
+        .limit(1)
+        .to_sql_with(&Sqlite)
+        .unwrap();
+    assert_eq!(
+        sql,
+        r#"SELECT * FROM (SELECT "users"."id" FROM "users" LIMIT ?) AS "__operand" LIMIT ?"#
+    );
+
+    let (sql, _) = a
+        .clone()
+        .union(b.clone().intersect(c.clone()))
+        .to_sql_with(&MySql)
+        .unwrap();
+    assert_eq!(
+        sql,
+        "SELECT `users`.`id` FROM `users` WHERE `users`.`id` = ? UNION SELECT `users`.`id` FROM `users` WHERE `users`.`id` = ? INTERSECT (SELECT `users`.`id` FROM `users` LIMIT ?)"
+    );
+}
+
+#[test]
+fn nested_set_operations_without_emulation() {
+    struct Flat;
+
+    impl Dialect for Flat {
+        fn name(&self) -> &'static str {
+            "Flat"
+        }
+
+        fn supports(&self, feature: Feature) -> bool {
+            feature != Feature::NestedSetOperations
+        }
+    }
+
+    let a = users::table.select(users::id);
+    let b = countries::table.select(countries::id);
+
+    assert!(a
+        .clone()
+        .union(b.clone())
+        .union(a.clone())
+        .to_sql_with(&Flat)
+        .is_ok());
+    assert_eq!(
+        a.clone().union(b.union(a)).to_sql_with(&Flat).unwrap_err(),
+        Error::Unsupported {
+            dialect: "Flat",
+            feature: Feature::NestedSetOperations,
+        }
+    );
+}
@@ -0,0 +1,90 @@
+use crate::*;
+
+table! {
+    events {
+        id -> Integer,
+        day -> Date,
+        created_at -> Timestamp,
+        updated_at -> Timestamptz,
+        deleted_at -> Nullable<Timestamptz>,
+        uuid -> Uuid,
+        meta -> Jsonb,
+    }
+}
+
+fn binds_of(query: Query<events::table>) -> Vec<Bind> {
+    query.select(events::id).to_sql().1.collect()
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn chrono_binds() {
+    use chrono::{DateTime, NaiveDate};
+
+    let day = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    let before_epoch = NaiveDate::from_ymd_opt(1969, 12, 31).unwrap();
+    let created_at = day.and_hms_micro_opt(12, 30, 0, 250).unwrap();
+    let updated_at = DateTime::from_timestamp(-1, 0).unwrap();
+
+    let binds = binds_of(
+        Query::from(events::table)
+            .filter(events::day.eq(day))
+            .filter(events::day.gt(before_epoch))
+            .filter(events::created_at.lt(created_at))
+            .filter(events::updated_at.ge(updated_at))
+            .filter(events::deleted_at.eq(Some(updated_at))),
+    );
+    assert_eq!(
+        binds,
+        vec![
+            Bind::Date(18_628),
+            Bind::Date(-1),
+            Bind::Timestamp(1_609_504_200_000_250),
+            Bind::Timestamptz(-1_000_000),
+            Bind::Timestamptz(-1_000_000),
+        ]
+    );
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn time_binds() {
+    use time::{Duration, OffsetDateTime, UtcOffset};
+
+    let updated_at = OffsetDateTime::UNIX_EPOCH + Duration::nanoseconds(1_500);
+    let before_epoch = OffsetDateTime::UNIX_EPOCH - Duration::nanoseconds(1_500);
+    let in_copenhagen = updated_at.to_offset(UtcOffset::from_hms(2, 0, 0).unwrap());
+
+    let binds = binds_of(
+        Query::from(events::table)
+            .filter(events::updated_at.eq(updated_at))
+            .filter(events::updated_at.gt(before_epoch))
+            .filter(events::updated_at.eq(in_copenhagen)),
+    );
+    assert_eq!(
+        binds,
+        vec![
+            Bind::Timestamptz(1),
+            Bind::Timestamptz(-2),
+            Bind::Timestamptz(1),
+        ]
+    );
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn uuid_binds() {
+    let uuid = uuid::Uuid::from_bytes([7; 16]);
+
+    let binds = binds_of(Query::from(events::table).filter(events::uuid.eq(uuid)));
+    assert_eq!(binds, vec![Bind::Uuid([7; 16])]);
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn serde_json_binds() {
+    let meta = serde_json::json!({ "admin": true });
+
+    let binds = binds_of(Query::from(events::table).filter(events::meta.eq(meta)));
+    assert_eq!(binds, vec![Bind::Jsonb(r#"{"admin":true}"#.to_string())]);
+}
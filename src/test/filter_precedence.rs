@@ -0,0 +1,115 @@
+// Renders random filter trees and checks that SQLite evaluates them the same way as the tree
+// itself, using SQL's three valued logic. Simplified filters must evaluate the same way too.
+
+use crate::*;
+use proptest::prelude::*;
+
+table! {
+    t {
+        id -> Integer,
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Tree {
+    Leaf(Option<bool>),
+    Const(bool),
+    And(Box<Tree>, Box<Tree>),
+    Or(Box<Tree>, Box<Tree>),
+    Not(Box<Tree>),
+}
+
+impl Tree {
+    fn eval(&self) -> Option<bool> {
+        match self {
+            Tree::Leaf(value) => *value,
+            Tree::Const(value) => Some(*value),
+            Tree::And(lhs, rhs) => match (lhs.eval(), rhs.eval()) {
+                (Some(false), _) | (_, Some(false)) => Some(false),
+                (Some(true), Some(true)) => Some(true),
+                _ => None,
+            },
+            Tree::Or(lhs, rhs) => match (lhs.eval(), rhs.eval()) {
+                (Some(true), _) | (_, Some(true)) => Some(true),
+                (Some(false), Some(false)) => Some(false),
+                _ => None,
+            },
+            Tree::Not(inner) => inner.eval().map(|value| !value),
+        }
+    }
+
+    fn to_filter(&self) -> Filter {
+        match self {
+            Tree::Leaf(value) => {
+                let rhs = match value {
+                    Some(true) => "1",
+                    Some(false) => "0",
+                    None => "NULL",
+                };
+                Filter::BinOp {
+                    lhs: Expr::Raw("1".to_string()),
+                    op: BinOp::Eq,
+                    rhs: Expr::Raw(rhs.to_string()),
+                }
+            }
+            Tree::Const(true) => Filter::true_(),
+            Tree::Const(false) => Filter::false_(),
+            Tree::And(lhs, rhs) => lhs.to_filter().and(rhs.to_filter()),
+            Tree::Or(lhs, rhs) => lhs.to_filter().or(rhs.to_filter()),
+            Tree::Not(inner) => inner.to_filter().not(),
+        }
+    }
+}
+
+fn tree() -> impl Strategy<Value = Tree> {
+    let leaf = prop_oneof![
+        prop_oneof![Just(Some(true)), Just(Some(false)), Just(None)].prop_map(Tree::Leaf),
+        any::<bool>().prop_map(Tree::Const),
+    ];
+    leaf.prop_recursive(6, 64, 2, |inner| {
+        prop_oneof![
+            (inner.clone(), inner.clone()).prop_map(|(a, b)| Tree::And(Box::new(a), Box::new(b))),
+            (inner.clone(), inner.clone()).prop_map(|(a, b)| Tree::Or(Box::new(a), Box::new(b))),
+            inner.prop_map(|a| Tree::Not(Box::new(a))),
+        ]
+    })
+}
+
+fn matches(query: Query<t::table>) -> (bool, String) {
+    let (sql, _) = query.select(t::id).to_sql_with(&Sqlite).unwrap();
+
+    let conn = rusqlite::Connection::open_in_memory().unwrap();
+    conn.execute_batch("CREATE TABLE t (id INTEGER); INSERT INTO t VALUES (1);")
+        .unwrap();
+    let matched = conn.prepare(&sql).unwrap().exists([]).unwrap();
+
+    (matched, sql)
+}
+
+proptest! {
+    #[test]
+    fn rendered_filters_match_reference_evaluation(tree in tree()) {
+        let (matched, sql) = matches(t::table.filter(tree.to_filter()));
+
+        prop_assert_eq!(matched, tree.eval() == Some(true), "{}", sql);
+    }
+
+    #[test]
+    fn merged_scopes_keep_their_meaning(lhs in tree(), rhs in tree()) {
+        let (matched, sql) = matches(
+            t::table
+                .filter(lhs.to_filter())
+                .merge(t::table.filter(rhs.to_filter())),
+        );
+
+        let expected = Tree::And(Box::new(lhs), Box::new(rhs)).eval() == Some(true);
+        prop_assert_eq!(matched, expected, "{}", sql);
+    }
+
+    #[test]
+    fn simplified_filters_keep_their_meaning(tree in tree()) {
+        let (matched, sql) = matches(t::table.filter(tree.to_filter()).simplify_filters());
+
+        prop_assert_eq!(matched, tree.eval() == Some(true), "{}", sql);
+    }
+}
@@ -0,0 +1,207 @@
+use crate::*;
+
+table! {
+    users {
+        id -> Integer,
+        name -> Text,
+        meta -> Jsonb,
+        tags -> Array<Text>,
+    }
+}
+
+table! {
+    posts {
+        id -> Integer,
+        user_id -> Integer,
+        title -> Text,
+    }
+}
+
+#[test]
+fn json_operators() {
+    let (sql, mut binds) = users::table
+        .filter(users::meta.get("address").get_text("city").eq("Copenhagen"))
+        .filter(users::meta.get_text("email").like("%@example.com"))
+        .filter(
+            users::meta
+                .get_path(&["a", "b"])
+                .get_index(-1)
+                .get_path_text(&["c"])
+                .ne("x"),
+        )
+        .select(users::id)
+        .to_sql();
+
+    assert_eq!(
+        sql,
+        r#"SELECT "users"."id" FROM "users" WHERE "users"."meta" -> $1 ->> $2 = $3 AND "users"."meta" ->> $4 LIKE $5 AND "users"."meta" #> $6 -> $7 #>> $8 != $9"#
+    );
+    assert_eq!(binds.next(), Some(Bind::String("address".to_string())));
+    assert_eq!(binds.next(), Some(Bind::String("city".to_string())));
+    assert_eq!(binds.next(), Some(Bind::String("Copenhagen".to_string())));
+    assert_eq!(binds.next(), Some(Bind::String("email".to_string())));
+    assert_eq!(
+        binds.next(),
+        Some(Bind::String("%@example.com".to_string()))
+    );
+    assert_eq!(
+        binds.next(),
+        Some(Bind::Array {
+            element_type: "text",
+            values: vec![Bind::String("a".to_string()), Bind::String("b".to_string())],
+        })
+    );
+    assert_eq!(binds.next(), Some(Bind::I32(-1)));
+}
+
+#[test]
+fn json_filters() {
+    let (sql, mut binds) = users::table
+        .filter(users::meta.contains(Value::jsonb(r#"{"admin":true}"#)))
+        .filter(users::meta.has_key("email"))
+        .filter(users::meta.has_any_key(&["a", "b"]))
+        .filter(users::meta.has_all_keys(&["c"]))
+        .filter(users::meta.path_exists("$.tags[*] ? (@ == \"x\")"))
+        .filter(users::tags.contains(vec!["admin"]))
+        .select(users::id)
+        .to_sql();
+
+    assert_eq!(
+        sql,
+        r#"SELECT "users"."id" FROM "users" WHERE "users"."meta" @> $1 AND "users"."meta" ? $2 AND "users"."meta" ?| $3 AND "users"."meta" ?& $4 AND jsonb_path_exists("users"."meta", $5) AND "users"."tags" @> $6"#
+    );
+    assert_eq!(
+        binds.next(),
+        Some(Bind::Jsonb(r#"{"admin":true}"#.to_string()))
+    );
+    assert_eq!(binds.next(), Some(Bind::String("email".to_string())));
+}
+
+#[test]
+fn json_operators_on_both_sides() {
+    let (sql, _) = users::table
+        .filter(
+            users::meta
+                .get_text("name")
+                .eq(users::meta.get("profile").get_text("name")),
+        )
+        .select(users::id)
+        .to_sql();
+
+    assert_eq!(
+        sql,
+        r#"SELECT "users"."id" FROM "users" WHERE "users"."meta" ->> $1 = "users"."meta" -> $2 ->> $3"#
+    );
+}
+
+#[test]
+fn build_objects_in_selects() {
+    let (sql, mut binds) = users::table
+        .inner_join(posts::table.on(posts::user_id.eq(users::id)))
+        .filter(posts::title.like("Hello%"))
+        .group_by(users::id)
+        .select((
+            users::id,
+            jsonb_build_object()
+                .field("id", users::id)
+                .field("name", users::name)
+                .alias("user"),
+            json_agg(
+                jsonb_build_object()
+                    .field("id", posts::id)
+                    .field("title", posts::title),
+            )
+            .alias("posts"),
+        ))
+        .to_sql();
+
+    assert_eq!(
+        sql,
+        r#"SELECT "users"."id", jsonb_build_object($1, "users"."id", $2, "users"."name") AS "user", json_agg(jsonb_build_object($3, "posts"."id", $4, "posts"."title")) AS "posts" FROM "users" INNER JOIN "posts" ON "posts"."user_id" = "users"."id" WHERE "posts"."title" LIKE $5 GROUP BY "users"."id""#
+    );
+    for expected in &["id", "name", "id", "title", "Hello%"] {
+        assert_eq!(binds.next(), Some(Bind::String(expected.to_string())));
+    }
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn select_without_alias() {
+    let (sql, _) = users::table
+        .select(jsonb_build_object().field("id", users::id))
+        .to_sql();
+    assert_eq!(
+        sql,
+        r#"SELECT jsonb_build_object($1, "users"."id") FROM "users""#
+    );
+
+    let (sql, _) = users::table.select(users::meta.get_text("name")).to_sql();
+    assert_eq!(sql, r#"SELECT "users"."meta" ->> $1 FROM "users""#);
+}
+
+#[test]
+fn json_operators_are_postgres_only() {
+    let unsupported = Error::Unsupported {
+        dialect: "MySQL",
+        feature: Feature::Jsonb,
+    };
+
+    let err = users::table
+        .filter(users::meta.get_text("name").eq("Bob"))
+        .select(users::id)
+        .to_sql_with(&MySql)
+        .unwrap_err();
+    assert_eq!(err, unsupported);
+
+    let err = users::table
+        .select(jsonb_build_object().field("id", users::id))
+        .to_sql_with(&MySql)
+        .unwrap_err();
+    assert_eq!(err, unsupported);
+
+    let err = users::table
+        .select(json_agg(users::name))
+        .to_sql_with(&MySql)
+        .unwrap_err();
+    assert_eq!(err, unsupported);
+
+    let err = users::table
+        .filter(users::meta.path_exists("$.name"))
+        .select(users::id)
+        .to_sql_with(&MySql)
+        .unwrap_err();
+    assert_eq!(err, unsupported);
+
+    let err = users::table
+        .filter(users::meta.contains(users::meta))
+        .select(users::id)
+        .to_sql_with(&MySql)
+        .unwrap_err();
+    assert_eq!(err, unsupported);
+
+    let err = users::table
+        .filter(users::meta.eq(Value::jsonb("{}")))
+        .select(users::id)
+        .to_sql_with(&Sqlite)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Error::Unsupported {
+            dialect: "SQLite",
+            feature: Feature::Jsonb,
+        }
+    );
+}
+
+#[test]
+fn nested_operators_are_parenthesized() {
+    // `@>` and `->` have the same precedence, so a nested rhs needs parentheses
+    let (sql, _) = users::table
+        .filter(users::meta.contains(users::meta.get("settings")))
+        .select(users::id)
+        .to_sql();
+    assert_eq!(
+        sql,
+        r#"SELECT "users"."id" FROM "users" WHERE "users"."meta" @> ("users"."meta" -> $1)"#
+    );
+}
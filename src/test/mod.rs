@@ -1,2 +1,29 @@
+mod arrays;
+mod count;
+mod custom_types;
+mod dialects;
+#[cfg(any(
+    feature = "chrono",
+    feature = "time",
+    feature = "uuid",
+    feature = "serde_json"
+))]
+mod external_types;
+mod filter_precedence;
+mod json;
+mod pagination;
+#[cfg(feature = "postgres-types")]
+mod postgres;
+mod row_values;
 mod scopes;
+mod search;
+mod simplify;
 mod sql_generation;
+#[cfg(feature = "rusqlite")]
+mod sqlite;
+#[cfg(feature = "sqlx-postgres")]
+mod sqlx_postgres;
+#[cfg(feature = "sqlx-sqlite")]
+mod sqlx_sqlite;
+mod temporal;
+mod types;
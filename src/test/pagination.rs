@@ -0,0 +1,250 @@
+use crate::*;
+
+table! {
+    posts {
+        id -> Integer,
+        title -> Text,
+        score -> Integer,
+        published_at -> Nullable<Timestamptz>,
+    }
+}
+
+#[test]
+fn first_page() {
+    let (sql, mut binds) = posts::table
+        .order_by(posts::id)
+        .paginate_after(None, 20)
+        .unwrap()
+        .select(posts::id)
+        .to_sql();
+
+    assert_eq!(
+        sql,
+        r#"SELECT "posts"."id" FROM "posts" ORDER BY "posts"."id" LIMIT $1"#
+    );
+    assert_eq!(binds.next(), Some(Bind::I32(20)));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn same_direction_uses_row_values() {
+    let cursor = Cursor::new().value(10).value(3);
+    let (sql, mut binds) = posts::table
+        .filter(posts::title.ne(""))
+        .order_by((posts::score.desc(), posts::id.desc()))
+        .paginate_after(Some(&cursor), 20)
+        .unwrap()
+        .select(posts::id)
+        .to_sql();
+
+    assert_eq!(
+        sql,
+        r#"SELECT "posts"."id" FROM "posts" WHERE "posts"."title" != $1 AND ("posts"."score", "posts"."id") < ($2, $3) ORDER BY "posts"."score" DESC, "posts"."id" DESC LIMIT $4"#
+    );
+    assert_eq!(binds.next(), Some(Bind::String("".to_string())));
+    assert_eq!(binds.next(), Some(Bind::I32(10)));
+    assert_eq!(binds.next(), Some(Bind::I32(3)));
+    assert_eq!(binds.next(), Some(Bind::I32(20)));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn mixed_directions() {
+    let cursor = Cursor::new().value(10).value(3);
+    let (sql, mut binds) = posts::table
+        .order_by((posts::score.desc(), posts::id))
+        .paginate_after(Some(&cursor), 20)
+        .unwrap()
+        .select(posts::id)
+        .to_sql();
+
+    assert_eq!(
+        sql,
+        r#"SELECT "posts"."id" FROM "posts" WHERE "posts"."score" < $1 OR "posts"."score" = $2 AND "posts"."id" > $3 ORDER BY "posts"."score" DESC, "posts"."id" LIMIT $4"#
+    );
+    assert_eq!(binds.next(), Some(Bind::I32(10)));
+    assert_eq!(binds.next(), Some(Bind::I32(10)));
+    assert_eq!(binds.next(), Some(Bind::I32(3)));
+    assert_eq!(binds.next(), Some(Bind::I32(20)));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn nulls_position() {
+    let paginate = |cursor: Cursor, order: Order| {
+        posts::table
+            .order_by(order)
+            .paginate_after(Some(&cursor), 20)
+            .unwrap()
+            .select(posts::id)
+            .to_sql()
+            .0
+    };
+
+    assert_eq!(
+        paginate(
+            Cursor::new().value(Value::timestamptz(0)).value(3),
+            (posts::published_at.nulls_last(), posts::id).into()
+        ),
+        r#"SELECT "posts"."id" FROM "posts" WHERE "posts"."published_at" > $1 OR "posts"."published_at" IS NULL OR "posts"."published_at" = $2 AND "posts"."id" > $3 ORDER BY "posts"."published_at" NULLS LAST, "posts"."id" LIMIT $4"#
+    );
+    assert_eq!(
+        paginate(
+            Cursor::new().nullable_value(None::<i32>).value(3),
+            (posts::published_at.nulls_last(), posts::id).into()
+        ),
+        r#"SELECT "posts"."id" FROM "posts" WHERE "posts"."published_at" IS NULL AND "posts"."id" > $1 ORDER BY "posts"."published_at" NULLS LAST, "posts"."id" LIMIT $2"#
+    );
+    assert_eq!(
+        paginate(
+            Cursor::new().nullable_value(None::<i32>).value(3),
+            (posts::published_at.desc().nulls_first(), posts::id).into()
+        ),
+        r#"SELECT "posts"."id" FROM "posts" WHERE "posts"."published_at" IS NOT NULL OR "posts"."published_at" IS NULL AND "posts"."id" > $1 ORDER BY "posts"."published_at" DESC NULLS FIRST, "posts"."id" LIMIT $2"#
+    );
+}
+
+#[test]
+fn unsupported_orders() {
+    let cursor = Cursor::new().value(1);
+
+    let err = Query::from(posts::table)
+        .paginate_after(Some(&cursor), 20)
+        .unwrap_err();
+    assert_eq!(err, Error::UnsupportedPaginationOrder);
+
+    let err = posts::table
+        .order_by(Order::raw("id"))
+        .paginate_after(None, 20)
+        .unwrap_err();
+    assert_eq!(err, Error::UnsupportedPaginationOrder);
+
+    // NULL needs to know where NULLs sort
+    let err = posts::table
+        .order_by(posts::published_at)
+        .paginate_after(Some(&Cursor::new().nullable_value(None::<i32>)), 20)
+        .unwrap_err();
+    assert_eq!(err, Error::UnsupportedPaginationOrder);
+
+    let err = posts::table
+        .order_by((posts::score, posts::id))
+        .paginate_after(Some(&cursor), 20)
+        .unwrap_err();
+    assert_eq!(err, Error::InvalidCursor);
+}
+
+#[test]
+fn cursor_tokens() {
+    let cursor = Cursor::new()
+        .value("Bob")
+        .value(1)
+        .value(3i16)
+        .value(4i64)
+        .value(true)
+        .value(1.5f32)
+        .value(2.5f64)
+        .value(Value::numeric("1.50"))
+        .value(vec![1u8, 2])
+        .value(Value::date(1))
+        .value(Value::time(2))
+        .value(Value::timestamp(3))
+        .value(Value::timestamptz(4))
+        .value(Value::uuid([7; 16]))
+        .value(Value::json("{}"))
+        .value(Value::jsonb("[]"))
+        .value(Interval::new(1, 2, 3))
+        .nullable_value(None::<i32>);
+
+    let token = cursor.encode().unwrap();
+    assert!(token
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+    assert_eq!(Cursor::decode(&token).unwrap(), cursor);
+
+    for len in 0..4 {
+        let cursor = Cursor::new().value("x".repeat(len));
+        assert_eq!(Cursor::decode(&cursor.encode().unwrap()).unwrap(), cursor);
+    }
+}
+
+#[test]
+fn invalid_cursor_tokens() {
+    assert_eq!(Cursor::decode(""), Err(Error::InvalidCursor));
+    assert_eq!(Cursor::decode("not a cursor"), Err(Error::InvalidCursor));
+
+    let token = Cursor::new().value("Bob").encode().unwrap();
+    assert_eq!(
+        Cursor::decode(&token[..token.len() - 2]),
+        Err(Error::InvalidCursor)
+    );
+
+    let cursor = Cursor::new().value(Bind::Custom {
+        type_name: "status",
+        bytes: b"active".to_vec(),
+    });
+    assert_eq!(cursor.encode(), Err(Error::InvalidCursor));
+}
+
+#[test]
+fn paginate_with_count() {
+    let (page, count) = posts::table
+        .filter(posts::score.gt(0))
+        .order_by(posts::id)
+        .limit(5)
+        .for_update()
+        .select(posts::id)
+        .paginate(3, 20);
+
+    let (sql, mut binds) = page.to_sql();
+    assert_eq!(
+        sql,
+        r#"SELECT "posts"."id" FROM "posts" WHERE "posts"."score" > $1 ORDER BY "posts"."id" LIMIT $2 OFFSET $3 FOR UPDATE"#
+    );
+    assert_eq!(binds.next(), Some(Bind::I32(0)));
+    assert_eq!(binds.next(), Some(Bind::I32(20)));
+    assert_eq!(binds.next(), Some(Bind::I32(40)));
+    assert_eq!(binds.next(), None);
+
+    let (sql, mut binds) = count.to_sql();
+    assert_eq!(
+        sql,
+        r#"SELECT count(*) FROM "posts" WHERE "posts"."score" > $1"#
+    );
+    assert_eq!(binds.next(), Some(Bind::I32(0)));
+    assert_eq!(binds.next(), None);
+
+    let (page, _) = posts::table.select(posts::id).paginate(0, 20);
+    assert_eq!(
+        page.to_sql().0,
+        r#"SELECT "posts"."id" FROM "posts" LIMIT $1 OFFSET $2"#
+    );
+
+    let (page, _) = posts::table.select(posts::id).paginate(3, 0);
+    let binds = page.to_sql().1.collect::<Vec<_>>();
+    assert_eq!(binds, vec![Bind::I32(1), Bind::I32(2)]);
+}
+
+#[test]
+fn paginate_grouped_and_distinct() {
+    let (_, count) = posts::table
+        .filter(posts::score.gt(0))
+        .group_by(posts::title)
+        .having(Filter::raw("count(*) > 1"))
+        .order_by(posts::title)
+        .select(posts::title)
+        .paginate(1, 20);
+    assert_eq!(
+        count.to_sql().0,
+        r#"SELECT count(*) FROM (SELECT "posts"."title" FROM "posts" WHERE "posts"."score" > $1 GROUP BY "posts"."title" HAVING count(*) > 1) "sub""#
+    );
+
+    let (_, count) = posts::table
+        .distinct()
+        .order_by(posts::title)
+        .select(posts::title)
+        .paginate(1, 20);
+    assert_eq!(
+        count.to_sql().0,
+        r#"SELECT count(*) FROM (SELECT DISTINCT "posts"."title" FROM "posts") "sub""#
+    );
+}
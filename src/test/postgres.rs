@@ -0,0 +1,227 @@
+use crate::*;
+use bytes::BytesMut;
+use postgres_types::{Format, Kind, ToSql, Type};
+
+table! {
+    users {
+        id -> Integer,
+        name -> Text,
+    }
+}
+
+#[test]
+fn binds_as_params() {
+    let (sql, binds) = users::table
+        .filter(users::name.eq("Bob"))
+        .filter(users::id.eq(1))
+        .select(users::id)
+        .to_sql();
+    assert_eq!(
+        sql,
+        r#"SELECT "users"."id" FROM "users" WHERE "users"."name" = $1 AND "users"."id" = $2"#
+    );
+    assert_eq!(binds.param_types(), vec![Type::TEXT, Type::INT4]);
+
+    let params = binds.as_params();
+    assert_eq!(params.len(), 2);
+
+    let mut out = BytesMut::new();
+    params[0].to_sql_checked(&Type::TEXT, &mut out).unwrap();
+    assert_eq!(&out[..], b"Bob");
+
+    let mut out = BytesMut::new();
+    params[1].to_sql_checked(&Type::INT4, &mut out).unwrap();
+    assert_eq!(&out[..], 1i32.to_be_bytes());
+}
+
+#[test]
+fn binds_are_checked_against_their_own_type() {
+    let mut out = BytesMut::new();
+    assert!(Bind::I32(1).to_sql_checked(&Type::TEXT, &mut out).is_err());
+    assert!(Bind::String("1".to_string())
+        .to_sql_checked(&Type::INT4, &mut out)
+        .is_err());
+}
+
+#[test]
+fn limit_and_offset_bind_as_int8() {
+    let (sql, binds) = users::table
+        .filter(users::name.eq("Bob"))
+        .limit(10)
+        .offset(20)
+        .select(users::id)
+        .to_sql();
+    assert_eq!(
+        sql,
+        r#"SELECT "users"."id" FROM "users" WHERE "users"."name" = $1 LIMIT $2 OFFSET $3"#
+    );
+
+    // The parameter types Postgres infers when preparing the statement
+    let prepared = [Type::TEXT, Type::INT8, Type::INT8];
+    let encoded = binds
+        .as_params()
+        .into_iter()
+        .zip(&prepared)
+        .map(|(param, ty)| {
+            let mut out = BytesMut::new();
+            param.to_sql_checked(ty, &mut out).unwrap();
+            out.to_vec()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        encoded,
+        vec![
+            b"Bob".to_vec(),
+            10i64.to_be_bytes().to_vec(),
+            20i64.to_be_bytes().to_vec()
+        ]
+    );
+}
+
+#[test]
+fn small_integers_widen() {
+    let encode = |bind: Bind, ty: &Type| {
+        let mut out = BytesMut::new();
+        bind.to_sql_checked(ty, &mut out).map(|_| out.to_vec())
+    };
+
+    assert_eq!(
+        encode(Bind::I16(3), &Type::INT4).unwrap(),
+        3i32.to_be_bytes().to_vec()
+    );
+    assert_eq!(
+        encode(Bind::I16(3), &Type::INT8).unwrap(),
+        3i64.to_be_bytes().to_vec()
+    );
+    assert!(encode(Bind::I32(3), &Type::INT2).is_err());
+    assert!(encode(Bind::I64(3), &Type::INT4).is_err());
+}
+
+#[test]
+fn u64_binds_must_fit_in_int8() {
+    let mut out = BytesMut::new();
+    Bind::U64(10).to_sql_checked(&Type::INT8, &mut out).unwrap();
+    assert_eq!(&out[..], 10i64.to_be_bytes());
+
+    assert!(Bind::U64(u64::MAX)
+        .to_sql_checked(&Type::INT8, &mut out)
+        .is_err());
+}
+
+#[test]
+fn encodes_every_type() {
+    let encode = |bind: Bind| {
+        let mut out = BytesMut::new();
+        bind.to_sql_checked(&bind.pg_type(), &mut out).unwrap();
+        out.to_vec()
+    };
+
+    assert_eq!(encode(Bind::I16(2)), 2i16.to_be_bytes());
+    assert_eq!(encode(Bind::I64(2)), 2i64.to_be_bytes());
+    assert_eq!(encode(Bind::Bool(true)), [1]);
+    assert_eq!(encode(Bind::F64(1.5)), 1.5f64.to_be_bytes());
+    assert_eq!(encode(Bind::Bytes(vec![1, 2])), [1, 2]);
+    assert_eq!(encode(Bind::Numeric("-1.50".to_string())), b"-1.50");
+    assert_eq!(encode(Bind::Date(10_958)), 1i32.to_be_bytes());
+    assert_eq!(encode(Bind::Time(1_000_000)), 1_000_000i64.to_be_bytes());
+    assert_eq!(
+        encode(Bind::Timestamptz(946_684_800_000_001)),
+        1i64.to_be_bytes()
+    );
+    assert_eq!(encode(Bind::Uuid([7; 16])), [7; 16]);
+    assert_eq!(encode(Bind::Json("{}".to_string())), b"{}");
+    assert_eq!(encode(Bind::Jsonb("{}".to_string())), b"\x01{}");
+    assert_eq!(
+        encode(Interval::new(1, 2, 3).into()),
+        [
+            3i64.to_be_bytes().to_vec(),
+            2i32.to_be_bytes().to_vec(),
+            1i32.to_be_bytes().to_vec()
+        ]
+        .concat()
+    );
+
+    let numeric = Bind::Numeric("1".to_string());
+    assert!(matches!(
+        numeric.encode_format(&Type::NUMERIC),
+        Format::Text
+    ));
+    assert!(Bind::Date(1)
+        .to_sql_checked(&Type::TIMESTAMP, &mut BytesMut::new())
+        .is_err());
+}
+
+#[test]
+fn custom_binds_are_checked_by_type_name() {
+    let bind = Bind::Custom {
+        type_name: "user_status",
+        bytes: b"active".to_vec(),
+    };
+    assert_eq!(bind.pg_type(), Type::UNKNOWN);
+
+    let labels = vec!["active".to_string(), "banned".to_string()];
+    let status = Type::new(
+        "user_status".to_string(),
+        16_500,
+        Kind::Enum(labels.clone()),
+        "public".to_string(),
+    );
+    let mut out = BytesMut::new();
+    bind.to_sql_checked(&status, &mut out).unwrap();
+    assert_eq!(&out[..], b"active");
+
+    let other = Type::new(
+        "other_status".to_string(),
+        16_501,
+        Kind::Enum(labels),
+        "public".to_string(),
+    );
+    assert!(bind.to_sql_checked(&other, &mut out).is_err());
+    assert!(bind.to_sql_checked(&Type::INT4, &mut out).is_err());
+}
+
+#[test]
+fn encodes_arrays() {
+    let bind = Bind::Array {
+        element_type: "int4",
+        values: vec![Bind::I32(1), Bind::I32(2)],
+    };
+    assert_eq!(bind.pg_type(), Type::INT4_ARRAY);
+
+    let mut out = BytesMut::new();
+    bind.to_sql_checked(&Type::INT4_ARRAY, &mut out).unwrap();
+    let mut expected = Vec::new();
+    for n in &[1i32, 0, 23, 2, 1, 4, 1, 4, 2] {
+        expected.extend_from_slice(&n.to_be_bytes());
+    }
+    assert_eq!(&out[..], &expected[..]);
+    assert!(bind
+        .to_sql_checked(&Type::TEXT_ARRAY, &mut BytesMut::new())
+        .is_err());
+    assert!(bind
+        .to_sql_checked(&Type::INT4, &mut BytesMut::new())
+        .is_err());
+
+    // Numeric elements are binary even though numeric binds are sent as text
+    let numerics = Bind::Array {
+        element_type: "numeric",
+        values: vec![Bind::Numeric("1".to_string())],
+    };
+    let mut out = BytesMut::new();
+    numerics
+        .to_sql_checked(&Type::NUMERIC_ARRAY, &mut out)
+        .unwrap();
+    assert_eq!(
+        &out[12..],
+        &[0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 10, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1]
+    );
+
+    let empty = Bind::Array {
+        element_type: "text",
+        values: vec![],
+    };
+    let mut out = BytesMut::new();
+    empty.to_sql_checked(&Type::TEXT_ARRAY, &mut out).unwrap();
+    assert_eq!(&out[8..12], &25u32.to_be_bytes());
+    assert_eq!(&out[12..16], &0i32.to_be_bytes());
+}
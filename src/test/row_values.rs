@@ -0,0 +1,81 @@
+use crate::*;
+
+table! {
+    users {
+        id -> Integer,
+        name -> Text,
+        created_at -> Timestamptz,
+    }
+}
+
+#[test]
+fn row_value_comparison() {
+    let (sql, mut binds) = users::table
+        .filter((users::created_at, users::id).gt((Value::timestamptz(1_000), 5)))
+        .select(users::id)
+        .to_sql();
+
+    assert_eq!(
+        sql,
+        r#"SELECT "users"."id" FROM "users" WHERE ("users"."created_at", "users"."id") > ($1, $2)"#
+    );
+    assert_eq!(binds.next(), Some(Bind::Timestamptz(1_000)));
+    assert_eq!(binds.next(), Some(Bind::I32(5)));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn in_lists() {
+    let (sql, mut binds) = users::table
+        .filter(users::id.is_in(vec![1, 2]))
+        .filter((users::id, users::name).is_in(vec![(3, "Bob"), (4, "Alice")]))
+        .select(users::id)
+        .to_sql();
+
+    assert_eq!(
+        sql,
+        r#"SELECT "users"."id" FROM "users" WHERE "users"."id" IN ($1, $2) AND ("users"."id", "users"."name") IN (($3, $4), ($5, $6))"#
+    );
+    assert_eq!(binds.next(), Some(Bind::I32(1)));
+    assert_eq!(binds.next(), Some(Bind::I32(2)));
+    assert_eq!(binds.next(), Some(Bind::I32(3)));
+    assert_eq!(binds.next(), Some(Bind::String("Bob".to_string())));
+    assert_eq!(binds.next(), Some(Bind::I32(4)));
+    assert_eq!(binds.next(), Some(Bind::String("Alice".to_string())));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn empty_in_list_is_false() {
+    let (sql, _) = users::table
+        .filter(users::id.is_in(Vec::<i32>::new()))
+        .select(users::id)
+        .to_sql();
+
+    assert_eq!(sql, r#"SELECT "users"."id" FROM "users" WHERE FALSE"#);
+}
+
+#[test]
+fn row_value_in_lists_use_values_on_sqlite() {
+    let (sql, _) = users::table
+        .filter((users::id, users::name).is_in(vec![(3, "Bob")]))
+        .select(users::id)
+        .to_sql_with(&Sqlite)
+        .unwrap();
+
+    assert_eq!(
+        sql,
+        r#"SELECT "users"."id" FROM "users" WHERE ("users"."id", "users"."name") IN (VALUES (?, ?))"#
+    );
+
+    let (sql, _) = users::table
+        .filter((users::id, users::name).is_in(vec![(3, "Bob")]))
+        .select(users::id)
+        .to_sql_with(&MySql)
+        .unwrap();
+
+    assert_eq!(
+        sql,
+        r#"SELECT `users`.`id` FROM `users` WHERE (`users`.`id`, `users`.`name`) IN ((?, ?))"#
+    );
+}
@@ -57,3 +57,20 @@ fn scopes() {
     assert_eq!(binds.next(), Some(Bind::I32(1)));
     assert_eq!(binds.next(), None);
 }
+
+#[test]
+fn merging_scopes_with_the_same_join() {
+    let (query, mut binds) = users::table
+        .in_country(1)
+        .merge(users::table.in_country(2))
+        .select(users::star)
+        .to_sql();
+
+    assert_eq!(
+        query,
+        r#"SELECT "users".* FROM "users" INNER JOIN "countries" ON "countries"."id" = "users"."country_id" WHERE "countries"."id" = $1 AND "countries"."id" = $2"#
+    );
+    assert_eq!(binds.next(), Some(Bind::I32(1)));
+    assert_eq!(binds.next(), Some(Bind::I32(2)));
+    assert_eq!(binds.next(), None);
+}
@@ -0,0 +1,90 @@
+use crate::*;
+
+table! {
+    posts {
+        id -> Integer,
+        title -> Text,
+        body -> Text,
+        published -> Bool,
+        search -> TsVector,
+    }
+}
+
+fn search(query: &str) -> Query<posts::table> {
+    let query = websearch_to_tsquery("english", query);
+    posts::table
+        .filter(to_tsvector("english", posts::body).matches(query.clone()))
+        .order_by(ts_rank(to_tsvector("english", posts::body), query).desc())
+}
+
+#[test]
+fn full_text_search() {
+    let (sql, mut binds) = posts::table
+        .filter(posts::published.eq(true))
+        .merge(search("rust -java"))
+        .select(posts::id)
+        .to_sql();
+
+    assert_eq!(
+        sql,
+        r#"SELECT "posts"."id" FROM "posts" WHERE "posts"."published" = $1 AND to_tsvector(CAST($2 AS regconfig), "posts"."body") @@ websearch_to_tsquery(CAST($3 AS regconfig), $4) ORDER BY ts_rank(to_tsvector(CAST($5 AS regconfig), "posts"."body"), websearch_to_tsquery(CAST($6 AS regconfig), $7)) DESC"#
+    );
+    assert_eq!(binds.next(), Some(Bind::Bool(true)));
+    assert_eq!(binds.next(), Some(Bind::String("english".to_string())));
+    assert_eq!(binds.next(), Some(Bind::String("english".to_string())));
+    assert_eq!(binds.next(), Some(Bind::String("rust -java".to_string())));
+    assert_eq!(binds.next(), Some(Bind::String("english".to_string())));
+    assert_eq!(binds.next(), Some(Bind::String("english".to_string())));
+    assert_eq!(binds.next(), Some(Bind::String("rust -java".to_string())));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn tsvector_columns() {
+    let (sql, mut binds) = posts::table
+        .filter(posts::search.matches(plainto_tsquery("simple", "hello world")))
+        .order_by((
+            ts_rank(posts::search, plainto_tsquery("simple", "hello")).desc(),
+            posts::id,
+        ))
+        .limit(10)
+        .select((
+            posts::id,
+            ts_rank(posts::search, plainto_tsquery("simple", "hello")).alias("rank"),
+        ))
+        .to_sql();
+
+    assert_eq!(
+        sql,
+        r#"SELECT "posts"."id", ts_rank("posts"."search", plainto_tsquery(CAST($1 AS regconfig), $2)) AS "rank" FROM "posts" WHERE "posts"."search" @@ plainto_tsquery(CAST($3 AS regconfig), $4) ORDER BY ts_rank("posts"."search", plainto_tsquery(CAST($5 AS regconfig), $6)) DESC, "posts"."id" LIMIT $7"#
+    );
+    assert_eq!(binds.next(), Some(Bind::String("simple".to_string())));
+    assert_eq!(binds.next(), Some(Bind::String("hello".to_string())));
+    assert_eq!(binds.next(), Some(Bind::String("simple".to_string())));
+    assert_eq!(binds.next(), Some(Bind::String("hello world".to_string())));
+    assert_eq!(binds.next(), Some(Bind::String("simple".to_string())));
+    assert_eq!(binds.next(), Some(Bind::String("hello".to_string())));
+    assert_eq!(binds.next(), Some(Bind::I32(10)));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn full_text_search_is_postgres_only() {
+    let query = posts::table
+        .filter(posts::search.matches(plainto_tsquery("simple", "hello")))
+        .select(posts::id);
+
+    let unsupported = Error::Unsupported {
+        dialect: "MySQL",
+        feature: Feature::FullTextSearch,
+    };
+    assert_eq!(query.to_sql_with(&MySql).unwrap_err(), unsupported);
+
+    let query = posts::table.select(to_tsvector("english", posts::body));
+    assert_eq!(query.to_sql_with(&MySql).unwrap_err(), unsupported);
+
+    let query = posts::table
+        .order_by(ts_rank(posts::search, websearch_to_tsquery("english", "hello")).desc())
+        .select(posts::id);
+    assert_eq!(query.to_sql_with(&MySql).unwrap_err(), unsupported);
+}
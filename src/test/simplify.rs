@@ -0,0 +1,110 @@
+use crate::*;
+
+table! {
+    users {
+        id -> Integer,
+        name -> Text,
+        country_id -> Integer,
+    }
+}
+
+fn sql(query: Query<users::table>) -> String {
+    query.select(users::id).to_sql().0
+}
+
+#[test]
+fn removes_duplicates_from_merged_scopes() {
+    let active = || users::table.filter(users::name.ne("banned"));
+    let query = active()
+        .merge(active().filter(users::country_id.eq(1)))
+        .merge(active())
+        .simplify_filters();
+
+    let (sql, mut binds) = query.select(users::id).to_sql();
+    assert_eq!(
+        sql,
+        r#"SELECT "users"."id" FROM "users" WHERE "users"."name" != $1 AND "users"."country_id" = $2"#
+    );
+    assert_eq!(binds.next(), Some(Bind::String("banned".to_string())));
+    assert_eq!(binds.next(), Some(Bind::I32(1)));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn folds_constants() {
+    assert_eq!(
+        sql(users::table
+            .filter(Filter::true_())
+            .filter(users::id.eq(1))
+            .simplify_filters()),
+        r#"SELECT "users"."id" FROM "users" WHERE "users"."id" = $1"#
+    );
+    assert_eq!(
+        sql(users::table
+            .filter(users::id.eq(1).or(Filter::true_()))
+            .filter(Filter::false_().not())
+            .simplify_filters()),
+        r#"SELECT "users"."id" FROM "users" WHERE TRUE"#
+    );
+    assert_eq!(
+        sql(users::table
+            .filter(users::id.eq(1))
+            .filter(Filter::false_().or(Filter::false_()))
+            .simplify_filters()),
+        r#"SELECT "users"."id" FROM "users" WHERE FALSE"#
+    );
+}
+
+#[test]
+fn flattens_and_cancels_double_negation() {
+    assert_eq!(
+        sql(users::table
+            .filter(
+                users::id
+                    .eq(1)
+                    .and(users::id.eq(2).and(users::id.eq(1)).not().not())
+            )
+            .simplify_filters()),
+        r#"SELECT "users"."id" FROM "users" WHERE "users"."id" = $1 AND "users"."id" = $2"#
+    );
+}
+
+#[test]
+fn merges_equalities_into_in() {
+    let (sql, mut binds) = users::table
+        .filter(
+            users::id
+                .eq(1)
+                .or(users::name.eq("Bob"))
+                .or(users::id.eq(2))
+                .or(users::id.eq(1)),
+        )
+        .filter(
+            users::country_id
+                .eq(1)
+                .or(users::country_id.eq_any(vec![2, 3])),
+        )
+        .simplify_filters()
+        .select(users::id)
+        .to_sql();
+
+    assert_eq!(
+        sql,
+        r#"SELECT "users"."id" FROM "users" WHERE ("users"."id" IN ($1, $2) OR "users"."name" = $3) AND ("users"."country_id" = $4 OR "users"."country_id" = ANY($5))"#
+    );
+    assert_eq!(binds.next(), Some(Bind::I32(1)));
+    assert_eq!(binds.next(), Some(Bind::I32(2)));
+    assert_eq!(binds.next(), Some(Bind::String("Bob".to_string())));
+    assert_eq!(binds.next(), Some(Bind::I32(1)));
+}
+
+#[test]
+fn leaves_raw_filters_alone() {
+    assert_eq!(
+        sql(users::table
+            .filter(Filter::raw("a = 1 OR a = 2"))
+            .filter(Filter::raw("a = 1 OR a = 2"))
+            .simplify_filters()),
+        r#"SELECT "users"."id" FROM "users" WHERE a = 1 OR a = 2"#
+    );
+}
@@ -5,6 +5,7 @@ table! {
         id -> Integer,
         name -> Text,
         country_id -> Integer,
+        email -> Nullable<Text>,
     }
 }
 
@@ -113,7 +114,7 @@ fn query_or_filter() {
 
     assert_eq!(
         query,
-        r#"SELECT "users".* FROM "users" WHERE ("users"."id" = $1) OR "users"."id" = $2"#
+        r#"SELECT "users".* FROM "users" WHERE "users"."id" = $1 OR "users"."id" = $2"#
     );
     assert_eq!(binds.next(), Some(Bind::I32(1)));
     assert_eq!(binds.next(), Some(Bind::I32(2)));
@@ -145,13 +146,121 @@ fn or_filter() {
 
     assert_eq!(
         query,
-        r#"SELECT "users".* FROM "users" WHERE ("users"."id" = $1) OR "users"."id" = $2"#
+        r#"SELECT "users".* FROM "users" WHERE "users"."id" = $1 OR "users"."id" = $2"#
     );
     assert_eq!(binds.next(), Some(Bind::I32(1)));
     assert_eq!(binds.next(), Some(Bind::I32(2)));
     assert_eq!(binds.next(), None);
 }
 
+#[test]
+fn filters_are_parenthesized_by_precedence() {
+    let (query, mut binds) = users::table
+        .filter(users::id.eq(1).or(users::id.eq(2)))
+        .filter(users::name.eq("Bob"))
+        .or_filter(users::id.eq(3).and(users::id.eq(4)))
+        .select(users::star)
+        .to_sql();
+
+    assert_eq!(
+        query,
+        r#"SELECT "users".* FROM "users" WHERE ("users"."id" = $1 OR "users"."id" = $2) AND "users"."name" = $3 OR "users"."id" = $4 AND "users"."id" = $5"#
+    );
+    assert_eq!(binds.next(), Some(Bind::I32(1)));
+    assert_eq!(binds.next(), Some(Bind::I32(2)));
+    assert_eq!(binds.next(), Some(Bind::String("Bob".to_string())));
+    assert_eq!(binds.next(), Some(Bind::I32(3)));
+    assert_eq!(binds.next(), Some(Bind::I32(4)));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn not_filter() {
+    let (query, mut binds) = users::table
+        .filter(users::id.eq(1).or(users::id.eq(2)).not())
+        .filter(!users::email.is_null())
+        .filter(Filter::raw("1 = 1 OR 1 = 2").not().not())
+        .select(users::star)
+        .to_sql();
+
+    assert_eq!(
+        query,
+        r#"SELECT "users".* FROM "users" WHERE NOT ("users"."id" = $1 OR "users"."id" = $2) AND NOT ("users"."email" IS NULL) AND NOT (NOT (1 = 1 OR 1 = 2))"#
+    );
+    assert_eq!(binds.next(), Some(Bind::I32(1)));
+    assert_eq!(binds.next(), Some(Bind::I32(2)));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn filter_all_and_any() {
+    let (query, mut binds) = users::table
+        .filter(Filter::all(vec![users::id.eq(1), users::name.eq("Bob")]))
+        .filter(Filter::any(vec![users::id.eq(2), users::id.eq(3)]))
+        .select(users::star)
+        .to_sql();
+
+    assert_eq!(
+        query,
+        r#"SELECT "users".* FROM "users" WHERE "users"."id" = $1 AND "users"."name" = $2 AND ("users"."id" = $3 OR "users"."id" = $4)"#
+    );
+    assert_eq!(binds.next(), Some(Bind::I32(1)));
+    assert_eq!(binds.next(), Some(Bind::String("Bob".to_string())));
+    assert_eq!(binds.next(), Some(Bind::I32(2)));
+    assert_eq!(binds.next(), Some(Bind::I32(3)));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn filter_all_and_any_of_nothing() {
+    let (query, _) = users::table
+        .filter(Filter::all(Vec::<Filter>::new()))
+        .or_filter(Filter::any(Vec::<Filter>::new()))
+        .select(users::star)
+        .to_sql();
+
+    assert_eq!(
+        query,
+        r#"SELECT "users".* FROM "users" WHERE TRUE OR FALSE"#
+    );
+}
+
+#[test]
+fn optional_filters() {
+    let name: Option<&str> = Some("Bob");
+    let country_id: Option<i32> = None;
+
+    let (query, mut binds) = users::table
+        .filter_opt(name.map(|name| users::name.eq(name)))
+        .filter_opt(country_id.map(|id| users::country_id.eq(id)))
+        .filter_if(true, users::id.gt(1))
+        .filter_if(false, users::id.lt(10))
+        .select(users::star)
+        .to_sql();
+
+    assert_eq!(
+        query,
+        r#"SELECT "users".* FROM "users" WHERE "users"."name" = $1 AND "users"."id" > $2"#
+    );
+    assert_eq!(binds.next(), Some(Bind::String("Bob".to_string())));
+    assert_eq!(binds.next(), Some(Bind::I32(1)));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn nested_raw_filters_are_parenthesized() {
+    let (query, _) = users::table
+        .filter(Filter::raw("1 = 1 OR 1 = 2"))
+        .filter(users::id.eq(1))
+        .select(users::star)
+        .to_sql();
+
+    assert_eq!(
+        query,
+        r#"SELECT "users".* FROM "users" WHERE (1 = 1 OR 1 = 2) AND "users"."id" = $1"#
+    );
+}
+
 #[test]
 fn inner_join() {
     let (query, mut binds) = users::table
@@ -226,6 +335,155 @@ fn merging_with_joins() {
     assert_eq!(binds.next(), None);
 }
 
+#[test]
+fn merging_identical_joins() {
+    let join = || countries::table.on(countries::id.eq(users::country_id).and(countries::id.gt(1)));
+    let a = users::table.inner_join(join());
+    let b = users::table.inner_join(join()).filter(users::id.gt(2));
+
+    let (query, mut binds) = a.merge(b).select(users::star).to_sql();
+
+    assert_eq!(
+        query,
+        r#"SELECT "users".* FROM "users" INNER JOIN "countries" ON "countries"."id" = "users"."country_id" AND "countries"."id" > $1 WHERE "users"."id" > $2"#
+    );
+    assert_eq!(binds.next(), Some(Bind::I32(1)));
+    assert_eq!(binds.next(), Some(Bind::I32(2)));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn merging_same_table_with_different_join_kinds() {
+    let join = || countries::table.on(countries::id.eq(users::country_id));
+    let a = users::table.inner_join(join());
+    let b = users::table.join(join());
+
+    let (query, _) = a.clone().merge(b.clone()).select(users::star).to_sql();
+    assert_eq!(
+        query,
+        r#"SELECT "users".* FROM "users" INNER JOIN "countries" ON "countries"."id" = "users"."country_id""#
+    );
+
+    let options = MergeOptions {
+        joins: MergePolicy::Combine,
+        ..MergeOptions::default()
+    };
+    assert_eq!(
+        a.merge_with(b, options).unwrap_err(),
+        Error::JoinConflict {
+            table: "countries".to_string()
+        }
+    );
+}
+
+#[test]
+fn merge_keeps_the_left_join_on_conflict() {
+    let a = users::table.inner_join(countries::table.on(countries::id.eq(users::country_id)));
+    let b = users::table.inner_join(countries::table.on(countries::id.eq(users::id)));
+
+    let (query, _) = a.merge(b).select(users::star).to_sql();
+    assert_eq!(
+        query,
+        r#"SELECT "users".* FROM "users" INNER JOIN "countries" ON "countries"."id" = "users"."country_id""#
+    );
+}
+
+#[test]
+fn merging_identical_sub_query_joins() {
+    let join = || {
+        countries::table
+            .limit(10)
+            .select(countries::id)
+            .alias("countries")
+            .on(countries::id.eq(users::country_id))
+    };
+
+    let (query, mut binds) = users::table
+        .join(join())
+        .merge(users::table.join(join()))
+        .select(users::star)
+        .to_sql();
+
+    assert_eq!(
+        query,
+        r#"SELECT "users".* FROM "users" JOIN (SELECT "countries"."id" FROM "countries" LIMIT $1) "countries" ON "countries"."id" = "users"."country_id""#
+    );
+    assert_eq!(binds.next(), Some(Bind::I32(10)));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn sub_queries_compare_structurally() {
+    let sub_query = |limit: i32| {
+        countries::table
+            .limit(limit)
+            .select(countries::id)
+            .alias("c")
+    };
+
+    assert_eq!(sub_query(10), sub_query(10));
+    assert_ne!(sub_query(10), sub_query(20));
+
+    let empty = users::table.select(Select::List(Vec::new())).alias("c");
+    assert_ne!(
+        empty.cast_to::<countries::table>(),
+        countries::table.select(Select::List(Vec::new())).alias("c")
+    );
+}
+
+#[test]
+fn merging_conflicting_joins() {
+    let a = users::table.inner_join(countries::table.on(countries::id.eq(users::country_id)));
+    let b = users::table.inner_join(countries::table.on(countries::id.eq(users::id)));
+
+    let result = a.clone().merge_with(
+        b.clone(),
+        MergeOptions {
+            joins: MergePolicy::Error,
+            ..MergeOptions::default()
+        },
+    );
+    assert_eq!(
+        result.unwrap_err(),
+        Error::JoinConflict {
+            table: "countries".to_string()
+        }
+    );
+
+    let (query, _) = a
+        .clone()
+        .merge_with(
+            b.clone(),
+            MergeOptions {
+                joins: MergePolicy::Combine,
+                ..MergeOptions::default()
+            },
+        )
+        .unwrap()
+        .select(users::star)
+        .to_sql();
+    assert_eq!(
+        query,
+        r#"SELECT "users".* FROM "users" INNER JOIN "countries" ON "countries"."id" = "users"."country_id" AND "countries"."id" = "users"."id""#
+    );
+
+    let (query, _) = a
+        .merge_with(
+            b,
+            MergeOptions {
+                joins: MergePolicy::KeepRight,
+                ..MergeOptions::default()
+            },
+        )
+        .unwrap()
+        .select(users::star)
+        .to_sql();
+    assert_eq!(
+        query,
+        r#"SELECT "users".* FROM "users" INNER JOIN "countries" ON "countries"."id" = "users"."id""#
+    );
+}
+
 #[test]
 fn limit() {
     let (query, mut binds) = users::table.limit(10).select(users::star).to_sql();
@@ -262,9 +520,10 @@ fn merge_limit_rhs() {
 
     assert_eq!(
         query,
-        r#"SELECT "users".* FROM "users" WHERE "users"."id" = $1"#
+        r#"SELECT "users".* FROM "users" WHERE "users"."id" = $1 LIMIT $2"#
     );
     assert_eq!(binds.next(), Some(Bind::I32(1)));
+    assert_eq!(binds.next(), Some(Bind::I32(10)));
     assert_eq!(binds.next(), None);
 }
 
@@ -281,6 +540,172 @@ fn merge_limit_both() {
     assert_eq!(binds.next(), None);
 }
 
+#[test]
+fn merge_limit_smallest() {
+    let options = MergeOptions {
+        limit: MergePolicy::Combine,
+        ..MergeOptions::default()
+    };
+
+    let (query, mut binds) = users::table
+        .limit(9999)
+        .merge_with(users::table.limit(10), options)
+        .unwrap()
+        .select(users::star)
+        .to_sql();
+
+    assert_eq!(query, r#"SELECT "users".* FROM "users" LIMIT $1"#);
+    assert_eq!(binds.next(), Some(Bind::I32(10)));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn merge_limit_rhs_wins() {
+    let options = MergeOptions {
+        limit: MergePolicy::KeepRight,
+        offset: MergePolicy::KeepRight,
+        ..MergeOptions::default()
+    };
+
+    let (query, mut binds) = users::table
+        .limit(5)
+        .offset(1)
+        .merge_with(users::table.limit(10).offset(2), options)
+        .unwrap()
+        .select(users::star)
+        .to_sql();
+
+    assert_eq!(query, r#"SELECT "users".* FROM "users" LIMIT $1 OFFSET $2"#);
+    assert_eq!(binds.next(), Some(Bind::I32(10)));
+    assert_eq!(binds.next(), Some(Bind::I32(2)));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn merge_limit_error() {
+    let options = MergeOptions {
+        limit: MergePolicy::Error,
+        ..MergeOptions::default()
+    };
+
+    let result = users::table
+        .limit(5)
+        .merge_with(users::table.limit(10), options);
+    assert_eq!(
+        result.unwrap_err(),
+        Error::MergeConflict { clause: "limit" }
+    );
+
+    let result = users::table.limit(Limit::raw("5")).merge_with(
+        users::table.limit(10),
+        MergeOptions {
+            limit: MergePolicy::Combine,
+            ..MergeOptions::default()
+        },
+    );
+    assert_eq!(
+        result.unwrap_err(),
+        Error::MergeConflict { clause: "limit" }
+    );
+}
+
+#[test]
+fn merge_order_by() {
+    let (query, mut binds) = users::table
+        .order_by(users::name)
+        .merge(users::table.order_by((users::id.desc(), users::country_id)))
+        .select(users::star)
+        .to_sql();
+
+    assert_eq!(
+        query,
+        r#"SELECT "users".* FROM "users" ORDER BY "users"."name", "users"."id" DESC, "users"."country_id""#
+    );
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn merge_group_by_and_having() {
+    let (query, mut binds) = users::table
+        .group_by(users::id)
+        .having(users::id.gt(1))
+        .merge(
+            users::table
+                .group_by(users::name)
+                .having(users::name.ne("Bob")),
+        )
+        .select(users::id)
+        .to_sql();
+
+    assert_eq!(
+        query,
+        r#"SELECT "users"."id" FROM "users" GROUP BY "users"."id", "users"."name" HAVING "users"."id" > $1 AND "users"."name" != $2"#
+    );
+    assert_eq!(binds.next(), Some(Bind::I32(1)));
+    assert_eq!(binds.next(), Some(Bind::String("Bob".to_string())));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn merge_rhs_only_clauses() {
+    let (query, mut binds) = users::table
+        .merge(
+            users::table
+                .distinct_on(users::id)
+                .order_by(users::id)
+                .offset(5)
+                .for_update()
+                .explain(),
+        )
+        .select(users::star)
+        .to_sql();
+
+    assert_eq!(
+        query,
+        r#"EXPLAIN SELECT DISTINCT ON ("users"."id") "users".* FROM "users" ORDER BY "users"."id" OFFSET $1 FOR UPDATE"#
+    );
+    assert_eq!(binds.next(), Some(Bind::I32(5)));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn merge_distinct_and_row_locking() {
+    let options = MergeOptions {
+        distinct: MergePolicy::Combine,
+        explain: MergePolicy::Combine,
+        ..MergeOptions::default()
+    };
+
+    let (query, mut binds) = users::table
+        .distinct_on(users::id)
+        .for_update()
+        .explain()
+        .merge_with(
+            users::table
+                .distinct_on((users::id, users::name))
+                .skip_locked()
+                .explain_analyze(),
+            options,
+        )
+        .unwrap()
+        .select(users::star)
+        .to_sql();
+
+    assert_eq!(
+        query,
+        r#"EXPLAIN ANALYZE SELECT DISTINCT ON ("users"."id", "users"."name") "users".* FROM "users" FOR UPDATE SKIP LOCKED"#
+    );
+    assert_eq!(binds.next(), None);
+
+    let result = users::table
+        .distinct()
+        .merge_with(users::table.distinct_on(users::id), options);
+    assert_eq!(
+        result.unwrap_err(),
+        Error::MergeConflict { clause: "distinct" }
+    );
+}
+
 #[test]
 fn merge_with_table() {
     // These should just type check. SQL generation covered by other tests
@@ -538,7 +963,7 @@ fn add_or_having() {
 
     assert_eq!(
         query,
-        r#"SELECT "users".* FROM "users" HAVING ("users"."id" = $1) OR "users"."id" = $2"#
+        r#"SELECT "users".* FROM "users" HAVING "users"."id" = $1 OR "users"."id" = $2"#
     );
     assert_eq!(binds.next(), Some(Bind::I32(1)));
     assert_eq!(binds.next(), Some(Bind::I32(2)));
@@ -819,13 +1244,13 @@ fn multiple_common_table_expression() {
 #[test]
 fn not_null() {
     let (sql, mut binds) = users::table
-        .filter(users::name.is_not_null())
+        .filter(users::email.is_not_null())
         .select(star())
         .to_sql();
 
     assert_eq!(
         sql,
-        r#"SELECT * FROM "users" WHERE "users"."name" IS NOT NULL"#
+        r#"SELECT * FROM "users" WHERE "users"."email" IS NOT NULL"#
     );
     assert_eq!(binds.next(), None);
 }
@@ -833,11 +1258,55 @@ fn not_null() {
 #[test]
 fn null() {
     let (sql, mut binds) = users::table
-        .filter(users::name.is_null())
+        .filter(users::email.is_null())
+        .select(star())
+        .to_sql();
+
+    assert_eq!(
+        sql,
+        r#"SELECT * FROM "users" WHERE "users"."email" IS NULL"#
+    );
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn nullable_columns_compare_with_non_null_values() {
+    let (sql, mut binds) = users::table
+        .filter(users::email.eq("bob@example.com"))
+        .filter(users::name.ne(users::email))
+        .select(star())
+        .to_sql();
+
+    assert_eq!(
+        sql,
+        r#"SELECT * FROM "users" WHERE "users"."email" = $1 AND "users"."name" != "users"."email""#
+    );
+    assert_eq!(
+        binds.next(),
+        Some(Bind::String("bob@example.com".to_string()))
+    );
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn option_binds() {
+    let (sql, mut binds) = users::table
+        .filter(users::email.eq(Some("bob@example.com")))
+        .filter(users::email.ne(None::<&str>))
+        .filter(users::id.eq(Some(1)))
+        .filter(users::email.eq(None::<String>))
         .select(star())
         .to_sql();
 
-    assert_eq!(sql, r#"SELECT * FROM "users" WHERE "users"."name" IS NULL"#);
+    assert_eq!(
+        sql,
+        r#"SELECT * FROM "users" WHERE "users"."email" = $1 AND "users"."email" IS NOT NULL AND "users"."id" = $2 AND "users"."email" IS NULL"#
+    );
+    assert_eq!(
+        binds.next(),
+        Some(Bind::String("bob@example.com".to_string()))
+    );
+    assert_eq!(binds.next(), Some(Bind::I32(1)));
     assert_eq!(binds.next(), None);
 }
 
@@ -910,3 +1379,377 @@ fn explain_analyze() {
     );
     assert_eq!(binds.next(), None);
 }
+
+#[test]
+fn intersect() {
+    let query = users::table.filter(users::id.eq(1)).select(users::id);
+    let query = query.clone().intersect(query.clone()).intersect_all(query);
+    let (sql, mut binds) = query.to_sql();
+
+    assert_eq!(
+        sql,
+        r#"SELECT "users"."id" FROM "users" WHERE "users"."id" = $1 INTERSECT SELECT "users"."id" FROM "users" WHERE "users"."id" = $2 INTERSECT ALL SELECT "users"."id" FROM "users" WHERE "users"."id" = $3"#
+    );
+    assert_eq!(binds.next(), Some(Bind::I32(1)));
+    assert_eq!(binds.next(), Some(Bind::I32(1)));
+    assert_eq!(binds.next(), Some(Bind::I32(1)));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn except() {
+    let query = users::table.select(users::id);
+    let query = query
+        .clone()
+        .except(query.clone())
+        .except_all(query.clone())
+        .except_distinct(query);
+    let (sql, mut binds) = query.to_sql();
+
+    assert_eq!(
+        sql,
+        r#"SELECT "users"."id" FROM "users" EXCEPT SELECT "users"."id" FROM "users" EXCEPT ALL SELECT "users"."id" FROM "users" EXCEPT DISTINCT SELECT "users"."id" FROM "users""#
+    );
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn set_operation_precedence() {
+    let a = users::table.filter(users::id.eq(1)).select(users::id);
+    let b = users::table.filter(users::id.eq(2)).select(users::id);
+    let c = users::table.filter(users::id.eq(3)).select(users::id);
+
+    // INTERSECT binds tighter than UNION so the lhs must be wrapped
+    let (sql, mut binds) = a.clone().union(b.clone()).intersect(c.clone()).to_sql();
+    assert_eq!(
+        sql,
+        r#"(SELECT "users"."id" FROM "users" WHERE "users"."id" = $1 UNION SELECT "users"."id" FROM "users" WHERE "users"."id" = $2) INTERSECT SELECT "users"."id" FROM "users" WHERE "users"."id" = $3"#
+    );
+    assert_eq!(binds.next(), Some(Bind::I32(1)));
+    assert_eq!(binds.next(), Some(Bind::I32(2)));
+    assert_eq!(binds.next(), Some(Bind::I32(3)));
+    assert_eq!(binds.next(), None);
+
+    let (sql, _) = a.clone().intersect(b.clone()).union(c.clone()).to_sql();
+    assert_eq!(
+        sql,
+        r#"SELECT "users"."id" FROM "users" WHERE "users"."id" = $1 INTERSECT SELECT "users"."id" FROM "users" WHERE "users"."id" = $2 UNION SELECT "users"."id" FROM "users" WHERE "users"."id" = $3"#
+    );
+
+    // Set operations are left associative so a nested rhs must be wrapped
+    let (sql, _) = a.except(b.union(c)).to_sql();
+    assert_eq!(
+        sql,
+        r#"SELECT "users"."id" FROM "users" WHERE "users"."id" = $1 EXCEPT (SELECT "users"."id" FROM "users" WHERE "users"."id" = $2 UNION SELECT "users"."id" FROM "users" WHERE "users"."id" = $3)"#
+    );
+}
+
+#[test]
+fn set_operation_order_and_limit() {
+    let a = users::table.filter(users::id.eq(1)).select(users::id);
+    let b = countries::table.select(countries::id);
+
+    let (sql, mut binds) = a
+        .union_all(b)
+        .order_by(users::id.desc())
+        .limit(10)
+        .offset(20)
+        .to_sql();
+
+    assert_eq!(
+        sql,
+        r#"SELECT "users"."id" FROM "users" WHERE "users"."id" = $1 UNION ALL SELECT "countries"."id" FROM "countries" ORDER BY "id" DESC LIMIT $2 OFFSET $3"#
+    );
+    assert_eq!(binds.next(), Some(Bind::I32(1)));
+    assert_eq!(binds.next(), Some(Bind::I32(10)));
+    assert_eq!(binds.next(), Some(Bind::I32(20)));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn set_operation_order_by_result_columns_only() {
+    let set = || {
+        users::table
+            .select(users::id)
+            .union(countries::table.select(countries::id))
+    };
+
+    let err = set()
+        .order_by(to_tsvector("english", users::name))
+        .try_to_sql()
+        .unwrap_err();
+    assert_eq!(err, Error::UnsupportedSetOperationOrder);
+
+    // Only the fallible methods check the ordering, `to_sql` leaves it to the database
+    let (sql, _) = set().order_by(to_tsvector("english", users::name)).to_sql();
+    assert_eq!(
+        sql,
+        r#"SELECT "users"."id" FROM "users" UNION SELECT "countries"."id" FROM "countries" ORDER BY to_tsvector(CAST($1 AS regconfig), "users"."name")"#
+    );
+
+    let (sql, _) = set().order_by(users::id.desc().nulls_last()).to_sql();
+    assert_eq!(
+        sql,
+        r#"SELECT "users"."id" FROM "users" UNION SELECT "countries"."id" FROM "countries" ORDER BY "id" DESC NULLS LAST"#
+    );
+
+    let err = set()
+        .order_by(users::id.nulls_last())
+        .to_sql_with(&MySql)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Error::Unsupported {
+            dialect: "MySQL",
+            feature: Feature::NullsPosition,
+        }
+    );
+}
+
+#[test]
+fn set_operation_operands_with_limit() {
+    let a = users::table.limit(1).select(users::id);
+    let b = users::table.order_by(users::id).limit(2).select(users::id);
+    let c = users::table.select(users::id);
+
+    let (sql, mut binds) = a.union(b).limit(3).union(c).to_sql();
+
+    assert_eq!(
+        sql,
+        r#"((SELECT "users"."id" FROM "users" LIMIT $1) UNION (SELECT "users"."id" FROM "users" ORDER BY "users"."id" LIMIT $2) LIMIT $3) UNION SELECT "users"."id" FROM "users""#
+    );
+    assert_eq!(binds.next(), Some(Bind::I32(1)));
+    assert_eq!(binds.next(), Some(Bind::I32(2)));
+    assert_eq!(binds.next(), Some(Bind::I32(3)));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn set_operation_sub_query() {
+    let set = users::table
+        .select(users::id)
+        .intersect(users::table.filter(users::id.gt(1)).select(users::id))
+        .limit(10);
+
+    let (sql, mut binds) = from(set.alias("users")).select(users::id).to_sql();
+
+    assert_eq!(
+        sql,
+        r#"SELECT "users"."id" FROM (SELECT "users"."id" FROM "users" INTERSECT SELECT "users"."id" FROM "users" WHERE "users"."id" > $1 LIMIT $2) "users""#
+    );
+    assert_eq!(binds.next(), Some(Bind::I32(1)));
+    assert_eq!(binds.next(), Some(Bind::I32(10)));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn set_operation_common_table_expression() {
+    let set = users::table.select(users::country_id).except(
+        countries::table
+            .filter(countries::name.eq("Denmark"))
+            .select(countries::id),
+    );
+
+    let (sql, mut binds) = users::table
+        .with(set.alias("country_ids"))
+        .filter(users::id.eq(1))
+        .select(users::star)
+        .to_sql();
+
+    assert_eq!(
+        sql,
+        r#"WITH "country_ids" AS (SELECT "users"."country_id" FROM "users" EXCEPT SELECT "countries"."id" FROM "countries" WHERE "countries"."name" = $1) SELECT "users".* FROM "users" WHERE "users"."id" = $2"#
+    );
+    assert_eq!(binds.next(), Some(Bind::String("Denmark".to_string())));
+    assert_eq!(binds.next(), Some(Bind::I32(1)));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn try_to_sql() {
+    let (sql, mut binds) = users::table
+        .distinct_on(users::id)
+        .order_by((users::id, users::name.desc()))
+        .filter(users::id.eq(1))
+        .for_update()
+        .skip_locked()
+        .select(users::star)
+        .try_to_sql()
+        .unwrap();
+
+    assert_eq!(
+        sql,
+        r#"SELECT DISTINCT ON ("users"."id") "users".* FROM "users" WHERE "users"."id" = $1 ORDER BY "users"."id", "users"."name" DESC FOR UPDATE SKIP LOCKED"#
+    );
+    assert_eq!(binds.next(), Some(Bind::I32(1)));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn try_to_sql_empty_select() {
+    let result = users::table.select(Select::List(Vec::new())).try_to_sql();
+
+    assert_eq!(result.unwrap_err(), Error::EmptySelect);
+}
+
+#[test]
+fn try_to_sql_distinct_on_without_matching_order_by() {
+    let result = users::table
+        .distinct_on(users::id)
+        .order_by((users::name, users::id))
+        .select(users::star)
+        .try_to_sql();
+    assert_eq!(result.unwrap_err(), Error::DistinctOnWithoutMatchingOrderBy);
+
+    let result = users::table
+        .distinct_on((users::id, users::name))
+        .order_by((users::name, users::id.desc(), users::country_id))
+        .select(users::star)
+        .try_to_sql();
+    assert!(result.is_ok());
+}
+
+#[test]
+fn try_to_sql_conflicting_row_locking() {
+    let result = users::table
+        .for_update()
+        .for_share()
+        .select(users::star)
+        .try_to_sql();
+    assert_eq!(result.unwrap_err(), Error::ConflictingRowLocking);
+
+    let result = users::table
+        .for_update()
+        .no_wait()
+        .skip_locked()
+        .select(users::star)
+        .try_to_sql();
+    assert_eq!(result.unwrap_err(), Error::ConflictingRowLocking);
+}
+
+#[test]
+fn try_to_sql_checks_sub_queries() {
+    let sub_query = users::table.for_update().for_key_share().select(users::id);
+    let result = from(sub_query.alias("users"))
+        .select(users::id)
+        .try_to_sql();
+    assert_eq!(result.unwrap_err(), Error::ConflictingRowLocking);
+
+    let cte = countries::table.select(Select::List(Vec::new()));
+    let result = users::table
+        .with(cte.alias("countries"))
+        .select(users::id)
+        .try_to_sql();
+    assert_eq!(result.unwrap_err(), Error::EmptySelect);
+
+    let result = users::table
+        .select(users::id)
+        .union(users::table.select(Select::List(Vec::new())))
+        .try_to_sql();
+    assert_eq!(result.unwrap_err(), Error::EmptySelect);
+}
+
+#[test]
+fn try_to_sql_too_many_binds() {
+    let query = (0..=u16::MAX as i32).fold(Query::from(users::table), |query, id| {
+        query.inner_join(countries::table.on(countries::id.eq(id)))
+    });
+
+    let result = query.select(users::id).try_to_sql();
+    assert_eq!(
+        result.unwrap_err(),
+        Error::TooManyBinds {
+            count: 65536,
+            max: 65535
+        }
+    );
+}
+
+#[test]
+fn validate() {
+    let query = users::table
+        .inner_join(countries::table.on(countries::id.eq(users::country_id)))
+        .filter(countries::name.eq("Denmark"))
+        .group_by((users::id, countries::id))
+        .having(countries::id.gt(1))
+        .order_by(users::name.desc())
+        .select((users::star, countries::name));
+    assert_eq!(query.validate(), Ok(()));
+
+    let query = users::table.select(countries::star);
+    assert_eq!(
+        query.validate(),
+        Err(Error::TableNotInScope {
+            table: "countries".to_string(),
+            column: None,
+        })
+    );
+
+    let query = users::table.order_by(countries::name).select(users::id);
+    assert_eq!(
+        query.validate(),
+        Err(Error::TableNotInScope {
+            table: "countries".to_string(),
+            column: Some("name".to_string()),
+        })
+    );
+
+    let sub_query = countries::table.select(countries::id).alias("c");
+    let query = users::table
+        .inner_join(sub_query.on(countries::id.eq(users::country_id)))
+        .select(users::id);
+    assert_eq!(
+        query.validate(),
+        Err(Error::TableNotInScope {
+            table: "countries".to_string(),
+            column: Some("id".to_string()),
+        })
+    );
+}
+
+#[test]
+fn validate_aliases() {
+    let sub_query = countries::table.select(countries::id).alias("countries");
+    let query = users::table
+        .with(sub_query)
+        .filter(countries::id.eq(users::country_id))
+        .select(users::id);
+    assert_eq!(query.validate(), Ok(()));
+
+    let sub_query = users::table.select(users::id).alias("u");
+    let query = from(sub_query).select(users::id);
+    assert_eq!(
+        query.validate(),
+        Err(Error::TableNotInScope {
+            table: "users".to_string(),
+            column: Some("id".to_string()),
+        })
+    );
+}
+
+#[test]
+fn validate_sub_queries() {
+    let sub_query = users::table
+        .filter(countries::id.eq(1))
+        .select(users::id)
+        .alias("users");
+    let query = from(sub_query).select(users::id);
+    assert_eq!(
+        query.validate(),
+        Err(Error::TableNotInScope {
+            table: "countries".to_string(),
+            column: Some("id".to_string()),
+        })
+    );
+
+    let query = users::table
+        .select(users::id)
+        .union(countries::table.select(users::id));
+    assert_eq!(
+        query.validate(),
+        Err(Error::TableNotInScope {
+            table: "users".to_string(),
+            column: Some("id".to_string()),
+        })
+    );
+}
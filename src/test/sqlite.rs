@@ -0,0 +1,422 @@
+use crate::*;
+use rusqlite::Connection;
+
+table! {
+    users {
+        id -> Integer,
+        name -> Text,
+        country_id -> Integer,
+    }
+}
+
+table! {
+    countries {
+        id -> Integer,
+        name -> Text,
+    }
+}
+
+fn setup() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch(
+        r#"
+        CREATE TABLE countries (id INTEGER PRIMARY KEY, name TEXT NOT NULL);
+        CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL, country_id INTEGER NOT NULL);
+        INSERT INTO countries VALUES (1, 'Denmark'), (2, 'Sweden');
+        INSERT INTO users VALUES (1, 'Bob', 1), (2, 'Alice', 1), (3, 'Bob', 2), (4, 'Eve', 2);
+        "#,
+    )
+    .unwrap();
+    conn
+}
+
+#[test]
+fn load() {
+    let conn = setup();
+
+    let names = users::table
+        .inner_join(countries::table.on(countries::id.eq(users::country_id)))
+        .filter(countries::name.eq("Denmark"))
+        .order_by(users::id)
+        .select(users::name)
+        .load(&conn, |row| row.get::<_, String>(0))
+        .unwrap();
+    assert_eq!(names, vec!["Bob", "Alice"]);
+
+    let ids = users::table
+        .filter(users::name.eq("Bob"))
+        .order_by(users::id.desc())
+        .limit(1)
+        .select(users::id)
+        .load(&conn, |row| row.get::<_, i32>(0))
+        .unwrap();
+    assert_eq!(ids, vec![3]);
+}
+
+#[test]
+fn load_emulated_features() {
+    let conn = setup();
+
+    let ids = users::table
+        .order_by(users::id)
+        .offset(2)
+        .select(users::id)
+        .load(&conn, |row| row.get::<_, i32>(0))
+        .unwrap();
+    assert_eq!(ids, vec![3, 4]);
+
+    let ids = users::table
+        .distinct_on(users::country_id)
+        .order_by((users::country_id, users::id.desc()))
+        .select(users::id)
+        .load(&conn, |row| row.get::<_, i32>(0))
+        .unwrap();
+    assert_eq!(ids, vec![2, 4]);
+}
+
+#[test]
+fn load_row_values() {
+    let conn = setup();
+
+    let ids = users::table
+        .filter((users::country_id, users::id).gt((1, 2)))
+        .order_by(users::id)
+        .select(users::id)
+        .load(&conn, |row| row.get::<_, i32>(0))
+        .unwrap();
+    assert_eq!(ids, vec![3, 4]);
+
+    let ids = users::table
+        .filter((users::name, users::country_id).is_in(vec![("Bob", 2), ("Alice", 1)]))
+        .order_by(users::id)
+        .select(users::id)
+        .load(&conn, |row| row.get::<_, i32>(0))
+        .unwrap();
+    assert_eq!(ids, vec![2, 3]);
+}
+
+#[test]
+fn load_pages_after_cursor() {
+    let conn = setup();
+
+    let orders: Vec<Order> = vec![
+        (users::name, users::id).into(),
+        (users::name.desc(), users::country_id, users::id.desc()).into(),
+    ];
+    for order in orders {
+        let all = users::table
+            .order_by(order.clone())
+            .select(users::id)
+            .load(&conn, |row| row.get::<_, i32>(0))
+            .unwrap();
+
+        let mut seen = vec![];
+        let mut token = None;
+        loop {
+            let cursor = token.as_deref().map(|t| Cursor::decode(t).unwrap());
+            let page = users::table
+                .order_by(order.clone())
+                .paginate_after(cursor.as_ref(), 3)
+                .unwrap()
+                .select((users::id, users::name, users::country_id))
+                .load(&conn, |row| {
+                    Ok((
+                        row.get::<_, i32>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, i32>(2)?,
+                    ))
+                })
+                .unwrap();
+
+            let (id, name, country_id) = match page.last() {
+                Some(last) => last.clone(),
+                None => break,
+            };
+            seen.extend(page.iter().map(|(id, _, _)| *id));
+            let cursor = match order.orderings().len() {
+                2 => Cursor::new().value(name).value(id),
+                _ => Cursor::new().value(name).value(country_id).value(id),
+            };
+            token = Some(cursor.encode().unwrap());
+        }
+
+        assert_eq!(seen, all);
+    }
+}
+
+#[test]
+fn load_page_with_count() {
+    let conn = setup();
+
+    let (page, count) = users::table
+        .order_by(users::id)
+        .select(users::name)
+        .paginate(2, 3);
+    let names = page.load(&conn, |row| row.get::<_, String>(0)).unwrap();
+    let count = count.load(&conn, |row| row.get::<_, i64>(0)).unwrap();
+    assert_eq!(names, vec!["Eve"]);
+    assert_eq!(count, vec![4]);
+
+    let (_, count) = users::table
+        .group_by(users::name)
+        .select(users::name)
+        .paginate(1, 3);
+    let count = count.load(&conn, |row| row.get::<_, i64>(0)).unwrap();
+    assert_eq!(count, vec![3]);
+}
+
+#[test]
+fn load_count_query() {
+    let conn = setup();
+
+    let counts = vec![
+        users::table.select(users::star).count_query(),
+        users::table
+            .group_by(users::country_id)
+            .select(users::country_id)
+            .count_query(),
+        users::table.distinct().select(users::name).count_query(),
+        users::table
+            .order_by(users::id)
+            .limit(2)
+            .offset(1)
+            .select(users::id)
+            .count_query(),
+    ]
+    .into_iter()
+    .map(|query| query.load(&conn, |row| row.get::<_, i64>(0)).unwrap())
+    .collect::<Vec<_>>();
+    assert_eq!(counts, vec![vec![4], vec![2], vec![3], vec![2]]);
+}
+
+#[test]
+fn load_distinct_on_ordered_by_expression() {
+    let conn = setup();
+
+    // The user closest to id 3 in each country, other than Eve
+    let distance_from = |id: i32| -> TypedExpr<sql_types::Integer> {
+        TypedExpr::new(Expr::Function {
+            name: "abs",
+            args: vec![Expr::BinOp {
+                lhs: Box::new(users::id.into_expr()),
+                op: BinOp::Sub,
+                rhs: Box::new(Expr::Bind(Bind::I32(id))),
+            }],
+        })
+    };
+    let ids = users::table
+        .filter(users::name.ne("Eve"))
+        .distinct_on(users::country_id)
+        .order_by((users::country_id.desc(), distance_from(3)))
+        .select(users::id)
+        .load(&conn, |row| row.get::<_, i32>(0))
+        .unwrap();
+    assert_eq!(ids, vec![3, 2]);
+}
+
+#[test]
+fn load_set_operation() {
+    let conn = setup();
+
+    let ids = users::table
+        .filter(users::name.eq("Bob"))
+        .select(users::id)
+        .union(users::table.filter(users::id.eq(4)).select(users::id))
+        .order_by(users::id)
+        .load(&conn, |row| row.get::<_, i32>(0))
+        .unwrap();
+    assert_eq!(ids, vec![1, 3, 4]);
+}
+
+#[test]
+fn load_nested_set_operations() {
+    let conn = setup();
+    let ids = |id: i32| users::table.filter(users::id.eq(id)).select(users::id);
+
+    let loaded = ids(1)
+        .union(ids(2).intersect(ids(3)))
+        .load(&conn, |row| row.get::<_, i32>(0))
+        .unwrap();
+    assert_eq!(loaded, vec![1]);
+
+    let loaded = ids(1)
+        .union(ids(2))
+        .intersect(ids(2))
+        .load(&conn, |row| row.get::<_, i32>(0))
+        .unwrap();
+    assert_eq!(loaded, vec![2]);
+
+    let loaded = users::table
+        .order_by(users::id.desc())
+        .limit(1)
+        .select(users::id)
+        .union(ids(1))
+        .order_by(users::id)
+        .load(&conn, |row| row.get::<_, i32>(0))
+        .unwrap();
+    assert_eq!(loaded, vec![1, 4]);
+}
+
+#[test]
+fn execute() {
+    let conn = setup();
+
+    let count = users::table
+        .filter(users::country_id.eq(2))
+        .select(users::star)
+        .execute(&conn)
+        .unwrap();
+    assert_eq!(count, 2);
+}
+
+#[test]
+fn errors() {
+    let conn = setup();
+
+    let err = users::table
+        .for_update()
+        .select(users::id)
+        .execute(&conn)
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        SqliteError::Query(Error::Unsupported {
+            feature: Feature::ForUpdate,
+            ..
+        })
+    ));
+
+    let err = from(users::table.select(users::id).alias("missing"))
+        .inner_join(countries::table.on(countries::id.eq(users::country_id)))
+        .select(countries::id)
+        .execute(&conn)
+        .unwrap_err();
+    assert!(matches!(err, SqliteError::Sqlite(_)));
+}
+
+table! {
+    events {
+        id -> BigInt,
+        public -> Bool,
+        score -> Double,
+        payload -> Bytea,
+        day -> Date,
+        starts_at -> Time,
+        created_at -> Timestamp,
+        uuid -> Uuid,
+    }
+}
+
+#[test]
+fn bind_types() {
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch(
+        r#"
+        CREATE TABLE events (id INTEGER, public BOOLEAN, score REAL, payload BLOB, day TEXT, starts_at TEXT, created_at TEXT, uuid BLOB);
+        INSERT INTO events VALUES (1, TRUE, 1.5, x'0102', '2021-01-01', '12:30:00', '2021-01-01 12:30:00.000250', x'07070707070707070707070707070707');
+        INSERT INTO events VALUES (2, FALSE, 2.5, x'03', '1969-12-31', '00:00:01', '1969-12-31 23:59:59', x'00');
+        "#,
+    )
+    .unwrap();
+
+    let load = |query: Query<events::table>| {
+        query
+            .select(events::id)
+            .load(&conn, |row| row.get::<_, i64>(0))
+            .unwrap()
+    };
+
+    assert_eq!(
+        load(Query::from(events::table).filter(events::id.eq(2i64))),
+        vec![2]
+    );
+    assert_eq!(
+        load(Query::from(events::table).filter(events::public.eq(true))),
+        vec![1]
+    );
+    assert_eq!(
+        load(Query::from(events::table).filter(events::score.gt(2.0))),
+        vec![2]
+    );
+    assert_eq!(
+        load(Query::from(events::table).filter(events::payload.eq(vec![1u8, 2]))),
+        vec![1]
+    );
+    assert_eq!(
+        load(Query::from(events::table).filter(events::day.eq(Value::date(18_628)))),
+        vec![1]
+    );
+    assert_eq!(
+        load(Query::from(events::table).filter(events::day.eq(Value::date(-1)))),
+        vec![2]
+    );
+    assert_eq!(
+        load(Query::from(events::table).filter(events::starts_at.eq(Value::time(45_000_000_000)))),
+        vec![1]
+    );
+    assert_eq!(
+        load(
+            Query::from(events::table)
+                .filter(events::created_at.eq(Value::timestamp(1_609_504_200_000_250)))
+        ),
+        vec![1]
+    );
+    assert_eq!(
+        load(
+            Query::from(events::table).filter(events::created_at.eq(Value::timestamp(-1_000_000)))
+        ),
+        vec![2]
+    );
+    assert_eq!(
+        load(Query::from(events::table).filter(events::uuid.eq(Value::uuid([7; 16])))),
+        vec![1]
+    );
+}
+
+#[derive(SqlEnum)]
+#[sql_enum(text)]
+pub enum Role {
+    Admin,
+    Member,
+}
+
+#[derive(SqlEnum)]
+#[sql_enum(type_name = "status")]
+pub enum Status {
+    Active,
+}
+
+table! {
+    members {
+        id -> Integer,
+        role -> Text,
+        status -> super::Status,
+    }
+}
+
+#[test]
+fn enum_binds() {
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch(
+        r#"
+        CREATE TABLE members (id INTEGER, role TEXT, status TEXT);
+        INSERT INTO members VALUES (1, 'admin', 'active'), (2, 'member', 'inactive');
+        "#,
+    )
+    .unwrap();
+
+    let ids = members::table
+        .filter(members::role.eq(Role::Member))
+        .filter(members::role.ne(Role::Admin))
+        .select(members::id)
+        .load(&conn, |row| row.get::<_, i32>(0))
+        .unwrap();
+    assert_eq!(ids, vec![2]);
+
+    let ids = members::table
+        .filter(members::status.eq(Status::Active))
+        .select(members::id)
+        .load(&conn, |row| row.get::<_, i32>(0))
+        .unwrap();
+    assert_eq!(ids, vec![1]);
+}
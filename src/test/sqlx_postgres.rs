@@ -0,0 +1,48 @@
+use crate::pg_encoding::encode_numeric;
+
+fn numeric(text: &str) -> Vec<u8> {
+    let mut buf = Vec::new();
+    encode_numeric(text, &mut buf).unwrap();
+    buf
+}
+
+fn header(ndigits: i16, weight: i16, sign: u16, dscale: u16) -> Vec<u8> {
+    [
+        ndigits.to_be_bytes(),
+        weight.to_be_bytes(),
+        sign.to_be_bytes(),
+        dscale.to_be_bytes(),
+    ]
+    .concat()
+}
+
+#[test]
+fn numeric_encoding() {
+    let mut expected = header(2, 0, 0, 2);
+    expected.extend_from_slice(&123i16.to_be_bytes());
+    expected.extend_from_slice(&4500i16.to_be_bytes());
+    assert_eq!(numeric("123.45"), expected);
+
+    let mut expected = header(2, 1, 0x4000, 0);
+    expected.extend_from_slice(&1i16.to_be_bytes());
+    expected.extend_from_slice(&2345i16.to_be_bytes());
+    assert_eq!(numeric("-12345"), expected);
+
+    let mut expected = header(1, -1, 0, 3);
+    expected.extend_from_slice(&50i16.to_be_bytes());
+    assert_eq!(numeric("0.005"), expected);
+
+    let mut expected = header(1, 1, 0, 0);
+    expected.extend_from_slice(&1i16.to_be_bytes());
+    assert_eq!(numeric("10000"), expected);
+
+    assert_eq!(numeric("0.00"), header(0, 0, 0, 2));
+    assert_eq!(numeric("-0"), header(0, 0, 0, 0));
+}
+
+#[test]
+fn invalid_numeric() {
+    for text in &["", ".", "1.2.3", "abc", "1e5"] {
+        assert!(encode_numeric(text, &mut Vec::new()).is_err(), "{}", text);
+    }
+}
@@ -0,0 +1,186 @@
+use crate::*;
+use sqlx::{Connection, Row, SqliteConnection};
+
+table! {
+    users {
+        id -> Integer,
+        name -> Text,
+        country_id -> Integer,
+    }
+}
+
+table! {
+    countries {
+        id -> Integer,
+        name -> Text,
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, sqlx::FromRow)]
+struct User {
+    id: i32,
+    name: String,
+}
+
+async fn setup() -> SqliteConnection {
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+    sqlx::raw_sql(
+        r#"
+        CREATE TABLE countries (id INTEGER PRIMARY KEY, name TEXT NOT NULL);
+        CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL, country_id INTEGER NOT NULL);
+        INSERT INTO countries VALUES (1, 'Denmark'), (2, 'Sweden');
+        INSERT INTO users VALUES (1, 'Bob', 1), (2, 'Alice', 1), (3, 'Bob', 2), (4, 'Eve', 2);
+        "#,
+    )
+    .execute(&mut conn)
+    .await
+    .unwrap();
+    conn
+}
+
+#[tokio::test]
+async fn into_sqlx() {
+    let mut conn = setup().await;
+
+    let rows = users::table
+        .inner_join(countries::table.on(countries::id.eq(users::country_id)))
+        .filter(countries::name.eq("Sweden"))
+        .filter(users::id.eq(4))
+        .select((users::id, countries::name))
+        .into_sqlx::<sqlx::Sqlite>()
+        .unwrap()
+        .fetch_all(&mut conn)
+        .await
+        .unwrap();
+
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].get::<i32, _>(0), 4);
+    assert_eq!(rows[0].get::<String, _>(1), "Sweden");
+}
+
+#[tokio::test]
+async fn into_sqlx_as() {
+    let mut conn = setup().await;
+
+    let users = users::table
+        .filter(users::name.eq("Bob"))
+        .order_by(users::id.desc())
+        .offset(1)
+        .select((users::id, users::name))
+        .into_sqlx_as::<sqlx::Sqlite, User>()
+        .unwrap()
+        .fetch_all(&mut conn)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        users,
+        vec![User {
+            id: 1,
+            name: "Bob".to_string()
+        }]
+    );
+}
+
+#[tokio::test]
+async fn set_operation_into_sqlx() {
+    let mut conn = setup().await;
+
+    let users = users::table
+        .filter(users::country_id.eq(1))
+        .select((users::id, users::name))
+        .union(
+            users::table
+                .filter(users::name.eq("Eve"))
+                .select((users::id, users::name)),
+        )
+        .order_by(users::id)
+        .limit(2)
+        .into_sqlx_as::<sqlx::Sqlite, User>()
+        .unwrap()
+        .fetch_all(&mut conn)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        users,
+        vec![
+            User {
+                id: 1,
+                name: "Bob".to_string()
+            },
+            User {
+                id: 2,
+                name: "Alice".to_string()
+            },
+        ]
+    );
+
+    let count = users::table
+        .filter(users::country_id.eq(2))
+        .select(users::id)
+        .except(users::table.filter(users::name.eq("Bob")).select(users::id))
+        .into_sqlx::<sqlx::Sqlite>()
+        .unwrap()
+        .fetch_all(&mut conn)
+        .await
+        .unwrap()
+        .len();
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn into_sqlx_checks_the_dialect() {
+    let result = users::table
+        .for_update()
+        .select(users::id)
+        .into_sqlx::<sqlx::Sqlite>();
+    assert_eq!(
+        result.err(),
+        Some(Error::Unsupported {
+            dialect: "SQLite",
+            feature: Feature::ForUpdate,
+        })
+    );
+}
+
+table! {
+    events {
+        id -> BigInt,
+        public -> Bool,
+        day -> Date,
+        created_at -> Timestamptz,
+        uuid -> Uuid,
+    }
+}
+
+#[tokio::test]
+async fn bind_types() {
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+    sqlx::raw_sql(
+        r#"
+        CREATE TABLE events (id INTEGER, public BOOLEAN, day TEXT, created_at TEXT, uuid BLOB);
+        INSERT INTO events VALUES (1, TRUE, '2021-01-01', '2021-01-01 00:00:01', x'07070707070707070707070707070707');
+        INSERT INTO events VALUES (2, FALSE, '2021-01-02', '2021-01-02 00:00:00', x'00');
+        "#,
+    )
+    .execute(&mut conn)
+    .await
+    .unwrap();
+
+    let rows = events::table
+        .filter(events::id.gt(0i64))
+        .filter(events::public.eq(true))
+        .filter(events::day.eq(Value::date(18_628)))
+        .filter(events::created_at.eq(Value::timestamptz(1_609_459_201_000_000)))
+        .filter(events::uuid.eq(Value::uuid([7; 16])))
+        .select(events::id)
+        .into_sqlx::<sqlx::Sqlite>()
+        .unwrap()
+        .fetch_all(&mut conn)
+        .await
+        .unwrap();
+
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].get::<i64, _>(0), 1);
+}
@@ -0,0 +1,167 @@
+use crate::*;
+
+table! {
+    orders {
+        id -> Integer,
+        created_at -> Timestamptz,
+        shipped_at -> Nullable<Timestamp>,
+        ordered_on -> Date,
+    }
+}
+
+fn created_in_the_last_days(days: i32) -> Query<orders::table> {
+    orders::table.filter(orders::created_at.gt(now().sub(Interval::days(days))))
+}
+
+#[test]
+fn interval_arithmetic() {
+    let (sql, mut binds) = created_in_the_last_days(7)
+        .filter(
+            orders::ordered_on
+                .add(Interval::months(1))
+                .lt(Value::timestamp(0)),
+        )
+        .select(orders::id)
+        .to_sql();
+
+    assert_eq!(
+        sql,
+        r#"SELECT "orders"."id" FROM "orders" WHERE "orders"."created_at" > CURRENT_TIMESTAMP - $1 AND "orders"."ordered_on" + $2 < $3"#
+    );
+    assert_eq!(
+        binds.next(),
+        Some(Bind::Interval {
+            months: 0,
+            days: 7,
+            micros: 0,
+        })
+    );
+    assert_eq!(
+        binds.next(),
+        Some(Bind::Interval {
+            months: 1,
+            days: 0,
+            micros: 0,
+        })
+    );
+    assert_eq!(binds.next(), Some(Bind::Timestamp(0)));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn interval_constructors() {
+    assert_eq!(Interval::years(2), Interval::new(24, 0, 0));
+    assert_eq!(Interval::weeks(2), Interval::new(0, 14, 0));
+    assert_eq!(Interval::hours(1), Interval::new(0, 0, 3_600_000_000));
+    assert_eq!(Interval::minutes(1), Interval::seconds(60));
+}
+
+#[test]
+fn date_functions() {
+    let (sql, mut binds) = orders::table
+        .filter(extract(DatePart::Year, orders::created_at).eq(2024.0))
+        .filter(orders::ordered_on.lt(current_date()))
+        .select((
+            orders::id,
+            date_trunc(DatePart::Day, orders::created_at).alias("day"),
+        ))
+        .to_sql();
+
+    assert_eq!(
+        sql,
+        r#"SELECT "orders"."id", date_trunc('day', "orders"."created_at") AS "day" FROM "orders" WHERE EXTRACT(YEAR FROM "orders"."created_at") = $1 AND "orders"."ordered_on" < CURRENT_DATE"#
+    );
+    assert_eq!(binds.next(), Some(Bind::F64(2024.0)));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn at_time_zone() {
+    let (sql, mut binds) = orders::table
+        .filter(
+            orders::shipped_at
+                .at_time_zone("UTC")
+                .at_time_zone("Europe/Copenhagen")
+                .is_not_null(),
+        )
+        .select(orders::id)
+        .to_sql();
+
+    assert_eq!(
+        sql,
+        r#"SELECT "orders"."id" FROM "orders" WHERE "orders"."shipped_at" AT TIME ZONE $1 AT TIME ZONE $2 IS NOT NULL"#
+    );
+    assert_eq!(binds.next(), Some(Bind::String("UTC".to_string())));
+    assert_eq!(
+        binds.next(),
+        Some(Bind::String("Europe/Copenhagen".to_string()))
+    );
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn operators_are_parenthesized_by_precedence() {
+    // `AT TIME ZONE` binds tighter than `-`
+    let (sql, _) = orders::table
+        .select(now().sub(Interval::days(7)).at_time_zone("UTC"))
+        .to_sql();
+    assert_eq!(
+        sql,
+        r#"SELECT (CURRENT_TIMESTAMP - $1) AT TIME ZONE $2 FROM "orders""#
+    );
+
+    let (sql, _) = orders::table
+        .select(
+            orders::created_at
+                .at_time_zone("UTC")
+                .sub(Interval::days(7)),
+        )
+        .to_sql();
+    assert_eq!(
+        sql,
+        r#"SELECT "orders"."created_at" AT TIME ZONE $1 - $2 FROM "orders""#
+    );
+
+    let (sql, _) = orders::table
+        .select(
+            orders::created_at
+                .add(Interval::days(1))
+                .sub(Interval::hours(1)),
+        )
+        .to_sql();
+    assert_eq!(
+        sql,
+        r#"SELECT "orders"."created_at" + $1 - $2 FROM "orders""#
+    );
+}
+
+#[test]
+fn intervals_are_postgres_only() {
+    let query = created_in_the_last_days(7).select(orders::id);
+
+    assert_eq!(
+        query.clone().to_sql_with(&Sqlite).unwrap_err(),
+        Error::Unsupported {
+            dialect: "SQLite",
+            feature: Feature::Intervals,
+        }
+    );
+    assert_eq!(
+        query.to_sql_with(&MySql).unwrap_err(),
+        Error::Unsupported {
+            dialect: "MySQL",
+            feature: Feature::Intervals,
+        }
+    );
+
+    let query = orders::table.select(date_trunc(DatePart::Day, orders::created_at));
+    for (dialect, name) in [(&MySql as &dyn Dialect, "MySQL"), (&Sqlite, "SQLite")] {
+        assert_eq!(
+            query.clone().to_sql_with(dialect).unwrap_err(),
+            Error::Unsupported {
+                dialect: name,
+                feature: Feature::DateTrunc,
+            }
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use crate::*;
+use std::time::{Duration, UNIX_EPOCH};
+
+table! {
+    events {
+        id -> BigInt,
+        kind -> SmallInt,
+        public -> Bool,
+        weight -> Float,
+        score -> Double,
+        price -> Numeric,
+        payload -> Bytea,
+        day -> Date,
+        starts_at -> Time,
+        created_at -> Timestamp,
+        updated_at -> Timestamptz,
+        uuid -> Uuid,
+        data -> Json,
+        meta -> Jsonb,
+    }
+}
+
+#[test]
+fn binds_for_every_type() {
+    let (sql, mut binds) = events::table
+        .filter(events::id.eq(1i64))
+        .filter(events::kind.eq(2i16))
+        .filter(events::public.eq(true))
+        .filter(events::weight.gt(1.5f32))
+        .filter(events::score.lt(2.5f64))
+        .filter(events::price.eq(Value::numeric("9.99")))
+        .filter(events::payload.eq(vec![1u8, 2]))
+        .filter(events::payload.ne(&b"abc"[..]))
+        .select(events::id)
+        .to_sql();
+
+    assert_eq!(
+        sql,
+        r#"SELECT "events"."id" FROM "events" WHERE "events"."id" = $1 AND "events"."kind" = $2 AND "events"."public" = $3 AND "events"."weight" > $4 AND "events"."score" < $5 AND "events"."price" = $6 AND "events"."payload" = $7 AND "events"."payload" != $8"#
+    );
+    assert_eq!(binds.next(), Some(Bind::I64(1)));
+    assert_eq!(binds.next(), Some(Bind::I16(2)));
+    assert_eq!(binds.next(), Some(Bind::Bool(true)));
+    assert_eq!(binds.next(), Some(Bind::F32(1.5)));
+    assert_eq!(binds.next(), Some(Bind::F64(2.5)));
+    assert_eq!(binds.next(), Some(Bind::Numeric("9.99".to_string())));
+    assert_eq!(binds.next(), Some(Bind::Bytes(vec![1, 2])));
+    assert_eq!(binds.next(), Some(Bind::Bytes(b"abc".to_vec())));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn binds_for_types_without_a_std_type() {
+    let (_, mut binds) = events::table
+        .filter(events::day.eq(Value::date(18_628)))
+        .filter(events::starts_at.ge(Value::time(3_600_000_000)))
+        .filter(events::created_at.lt(Value::timestamp(1_000_000)))
+        .filter(events::updated_at.gt(UNIX_EPOCH + Duration::from_secs(2)))
+        .filter(events::updated_at.gt(UNIX_EPOCH - Duration::from_secs(2)))
+        .filter(events::uuid.eq(Value::uuid([7; 16])))
+        .filter(events::data.eq(Value::json(r#"{"a":1}"#)))
+        .filter(events::meta.eq(Value::jsonb("[]")))
+        .select(events::id)
+        .to_sql();
+
+    assert_eq!(binds.next(), Some(Bind::Date(18_628)));
+    assert_eq!(binds.next(), Some(Bind::Time(3_600_000_000)));
+    assert_eq!(binds.next(), Some(Bind::Timestamp(1_000_000)));
+    assert_eq!(binds.next(), Some(Bind::Timestamptz(2_000_000)));
+    assert_eq!(binds.next(), Some(Bind::Timestamptz(-2_000_000)));
+    assert_eq!(binds.next(), Some(Bind::Uuid([7; 16])));
+    assert_eq!(binds.next(), Some(Bind::Json(r#"{"a":1}"#.to_string())));
+    assert_eq!(binds.next(), Some(Bind::Jsonb("[]".to_string())));
+    assert_eq!(binds.next(), None);
+}
+
+#[test]
+fn float_binds_compare_by_bits() {
+    assert_eq!(Bind::F64(f64::NAN), Bind::F64(f64::NAN));
+    assert_ne!(Bind::F64(0.0), Bind::F64(-0.0));
+    assert_ne!(Bind::F32(1.0), Bind::F64(1.0));
+}
@@ -0,0 +1,94 @@
+use crate::{Column, Error, Join, QueryWithSelect, Table};
+
+pub(crate) trait CollectTableRefs {
+    fn collect_table_refs<'a>(&'a self, refs: &mut Vec<TableRef<'a>>);
+}
+
+pub(crate) enum TableRef<'a> {
+    Column(&'a Column),
+    Table(&'a Table),
+}
+
+impl TableRef<'_> {
+    fn table(&self) -> &str {
+        match self {
+            TableRef::Column(col) => col.table,
+            TableRef::Table(table) => table.name(),
+        }
+    }
+
+    fn into_error(self) -> Error {
+        match self {
+            TableRef::Column(col) => Error::TableNotInScope {
+                table: col.table.to_string(),
+                column: Some(col.name.to_string()),
+            },
+            TableRef::Table(table) => Error::TableNotInScope {
+                table: table.name().to_string(),
+                column: None,
+            },
+        }
+    }
+}
+
+impl<T> QueryWithSelect<T> {
+    /// Check that every table referenced in the select list, join conditions, `WHERE`,
+    /// `GROUP BY`, `HAVING`, `ORDER BY` and `DISTINCT ON` is brought into scope by `FROM`, a join or a CTE.
+    ///
+    /// Sub queries are validated as well. Raw SQL is not inspected.
+    pub fn validate(&self) -> Result<(), Error> {
+        let query = &self.query;
+
+        let mut in_scope = vec![query.from.name()];
+        for join in &query.joins {
+            if let Join::Known { from, .. } = join {
+                in_scope.push(from.name());
+            }
+        }
+        for sub_query in query.ctes.iter() {
+            in_scope.push(&sub_query.alias);
+        }
+
+        let mut refs = Vec::new();
+        self.selection.collect_table_refs(&mut refs);
+        for join in &query.joins {
+            if let Join::Known { filter, .. } = join {
+                filter.collect_table_refs(&mut refs);
+            }
+        }
+        if let Some(distinct) = &query.distinct {
+            distinct.collect_table_refs(&mut refs);
+        }
+        if let Some(filter) = &query.filter {
+            filter.collect_table_refs(&mut refs);
+        }
+        if let Some(group) = &query.group {
+            group.collect_table_refs(&mut refs);
+        }
+        if let Some(having) = &query.having {
+            having.collect_table_refs(&mut refs);
+        }
+        if let Some(order) = &query.order {
+            order.collect_table_refs(&mut refs);
+        }
+
+        if let Some(table_ref) = refs
+            .into_iter()
+            .find(|table_ref| !in_scope.contains(&table_ref.table()))
+        {
+            return Err(table_ref.into_error());
+        }
+
+        for sub_query in query.ctes.iter() {
+            sub_query.query.validate()?;
+        }
+        query.from.validate()?;
+        for join in &query.joins {
+            if let Join::Known { from, .. } = join {
+                from.validate()?;
+            }
+        }
+
+        Ok(())
+    }
+}
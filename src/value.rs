@@ -0,0 +1,86 @@
+use crate::binds::Bind;
+use crate::sql_types::{Date, Json, Jsonb, Numeric, Time, Timestamp, Timestamptz, Uuid};
+use crate::{Expr, IntoExpr};
+use std::marker::PhantomData;
+
+/// A bind parameter of a SQL type that has no matching Rust type in `std`, such as
+/// `Value::date(18_628)` for 2021-01-01.
+#[derive(Debug, Clone)]
+pub struct Value<T> {
+    bind: Bind,
+    _marker: PhantomData<T>,
+}
+
+impl<T> Value<T> {
+    fn new(bind: Bind) -> Self {
+        Self {
+            bind,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl Value<Numeric> {
+    /// A decimal number such as `"-12.50"`. The text is sent as is.
+    pub fn numeric(value: impl Into<String>) -> Self {
+        Self::new(Bind::Numeric(value.into()))
+    }
+}
+
+impl Value<Date> {
+    pub fn date(days_since_epoch: i32) -> Self {
+        Self::new(Bind::Date(days_since_epoch))
+    }
+}
+
+impl Value<Time> {
+    pub fn time(micros_since_midnight: i64) -> Self {
+        Self::new(Bind::Time(micros_since_midnight))
+    }
+}
+
+impl Value<Timestamp> {
+    pub fn timestamp(micros_since_epoch: i64) -> Self {
+        Self::new(Bind::Timestamp(micros_since_epoch))
+    }
+}
+
+impl Value<Timestamptz> {
+    pub fn timestamptz(micros_since_epoch: i64) -> Self {
+        Self::new(Bind::Timestamptz(micros_since_epoch))
+    }
+}
+
+impl Value<Uuid> {
+    pub fn uuid(bytes: [u8; 16]) -> Self {
+        Self::new(Bind::Uuid(bytes))
+    }
+}
+
+impl Value<Json> {
+    /// Serialized JSON. The text is sent as is.
+    pub fn json(value: impl Into<String>) -> Self {
+        Self::new(Bind::Json(value.into()))
+    }
+}
+
+impl Value<Jsonb> {
+    /// Serialized JSON. The text is sent as is.
+    pub fn jsonb(value: impl Into<String>) -> Self {
+        Self::new(Bind::Jsonb(value.into()))
+    }
+}
+
+impl<T> From<Value<T>> for Bind {
+    fn from(value: Value<T>) -> Self {
+        value.bind
+    }
+}
+
+impl<T> IntoExpr for Value<T> {
+    type SqlType = T;
+
+    fn into_expr(self) -> Expr {
+        Expr::Bind(self.bind)
+    }
+}